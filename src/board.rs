@@ -0,0 +1,238 @@
+use clap::Parser;
+use colored::Colorize;
+use serde::Serialize;
+use terminal_size::{terminal_size, Width};
+use umsebenzi::enums::Status;
+use umsebenzi::filter::{subtask_progress, TaskFilter};
+use umsebenzi::response::TaskResponse;
+
+use crate::cli_error::Result;
+use crate::output::print_list;
+use crate::service::get_client;
+use crate::GlobalArgs;
+
+// Used when the width can't be read from the terminal or $COLUMNS
+const DEFAULT_WIDTH: usize = 80;
+const MIN_COLUMN_WIDTH: usize = 12;
+const SEPARATOR: &str = " | ";
+
+#[derive(Parser, Debug)]
+pub struct BoardArgs {
+    /// Project code
+    #[arg(short, long)]
+    project: Option<String>,
+    /// Include the ARCHIVE column
+    #[arg(long)]
+    archived: bool,
+}
+
+#[derive(Serialize)]
+struct Card {
+    code: String,
+    title: String,
+    // Complete subtasks out of all subtasks, epics with subtasks only
+    progress: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Column {
+    status: Status,
+    tasks: Vec<Card>,
+}
+
+fn terminal_width() -> usize {
+    if let Some((Width(w), _)) = terminal_size() {
+        return w.into();
+    }
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+fn columns(tasks: Vec<TaskResponse>, archived: bool) -> Vec<Column> {
+    let mut columns: Vec<Column> = Status::ALL
+        .iter()
+        .filter(|s| archived || **s != Status::ARCHIVE)
+        .map(|s| Column {
+            status: *s,
+            tasks: Vec::new(),
+        })
+        .collect();
+    for task in tasks {
+        let Some(column) = columns.iter_mut().find(|c| c.status == task.status) else {
+            continue;
+        };
+        column.tasks.push(Card {
+            progress: subtask_progress(&task)
+                .filter(|(_, all)| *all > 0)
+                .map(|(done, all)| format!("{done}/{all}")),
+            code: task.code,
+            title: task.title,
+        });
+    }
+    columns
+}
+
+// Cut to width, ending with '…' when cut, and pad with spaces
+fn fit(text: &str, width: usize) -> String {
+    let len = text.chars().count();
+    if len <= width {
+        return format!("{text}{}", " ".repeat(width - len));
+    }
+    let mut cut: String = text.chars().take(width.saturating_sub(1)).collect();
+    cut.push('…');
+    cut
+}
+
+// Width of each column, empty columns are dropped when they don't all fit.
+// The columns that still don't fit are returned to be named under the board
+fn layout(columns: &mut Vec<Column>, width: usize) -> (usize, Vec<Column>) {
+    let column_width = |n: usize| width.saturating_sub(SEPARATOR.len() * (n - 1)) / n;
+    if column_width(columns.len()) < MIN_COLUMN_WIDTH {
+        columns.retain(|c| !c.tasks.is_empty());
+    }
+    if columns.is_empty() {
+        return (width, Vec::new());
+    }
+    let fits = ((width + SEPARATOR.len()) / (MIN_COLUMN_WIDTH + SEPARATOR.len())).max(1);
+    let hidden = columns.split_off(fits.min(columns.len()));
+    (column_width(columns.len()), hidden)
+}
+
+// Board rows without the header, each card is its code and progress over its title
+fn card_rows(columns: &[Column], width: usize) -> Vec<String> {
+    let cards = columns.iter().map(|c| c.tasks.len()).max().unwrap_or(0);
+    let mut rows = Vec::new();
+    for i in 0..cards {
+        let mut first = Vec::new();
+        let mut second = Vec::new();
+        for column in columns {
+            match column.tasks.get(i) {
+                Some(card) => {
+                    let code = match &card.progress {
+                        Some(p) => format!("{} {p}", card.code),
+                        None => card.code.clone(),
+                    };
+                    first.push(fit(&code, width));
+                    second.push(fit(&card.title, width));
+                }
+                None => {
+                    first.push(fit("", width));
+                    second.push(fit("", width));
+                }
+            }
+        }
+        rows.push(first.join(SEPARATOR).trim_end().to_string());
+        rows.push(second.join(SEPARATOR).trim_end().to_string());
+        rows.push(String::new());
+    }
+    rows
+}
+
+pub fn run(args: BoardArgs, global: &GlobalArgs) -> Result<()> {
+    let client = get_client(global)?;
+    let filter = TaskFilter {
+        project: args.project,
+        ..Default::default()
+    };
+    let mut columns = columns(client.list_tasks(&filter)?, args.archived);
+    if !global.output.is_table() {
+        return print_list(&columns, global.output);
+    }
+    let (width, hidden) = layout(&mut columns, terminal_width());
+    if columns.is_empty() {
+        println!("No tasks");
+        return Ok(());
+    }
+    // Statuses are coloured, so padding is worked out from the plain name
+    let header: Vec<String> = columns
+        .iter()
+        .map(|c| {
+            let name = format!("{:?} ({})", c.status, c.tasks.len());
+            let plain = fit(&name, width);
+            match plain.strip_prefix(&format!("{:?}", c.status)) {
+                Some(rest) => format!("{}{rest}", c.status),
+                None => plain,
+            }
+        })
+        .collect();
+    println!("{}", header.join(SEPARATOR).trim_end());
+    let rule = vec!["-".repeat(width); columns.len()].join(SEPARATOR);
+    println!("{rule}");
+    for row in card_rows(&columns, width) {
+        println!("{row}");
+    }
+    if !hidden.is_empty() {
+        let names: Vec<String> = hidden
+            .iter()
+            .map(|c| format!("{:?} ({})", c.status, c.tasks.len()))
+            .collect();
+        eprintln!(
+            "{}: {} don't fit, widen the terminal to see them",
+            "Warning".yellow().bold(),
+            names.join(", ")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn task(code: &str, issue: &str, status: &str, subtasks: &[&str]) -> TaskResponse {
+        fixtures::task(1)
+            .set("title", format!("{code} title"))
+            .set("code", code)
+            .set("issue", issue)
+            .set("status", status)
+            .subtasks(subtasks)
+            .build()
+    }
+
+    #[test]
+    fn board_fits_the_width() {
+        let tasks = vec![
+            task("WEB-1", "EPIC", "TO_DO", &["COMPLETE", "DRAFT"]),
+            task("WEB-2", "SUBTASK", "REVIEW", &[]),
+            task("WEB-3", "SUBTASK", "ARCHIVE", &[]),
+        ];
+        let mut columns = columns(tasks, false);
+        assert_eq!(columns.len(), 6);
+        // Six columns don't fit in 60, only the two with tasks are kept
+        let (width, hidden) = layout(&mut columns, 60);
+        assert_eq!(columns.len(), 2);
+        assert!(hidden.is_empty());
+        assert_eq!(width, 28);
+        let rows = card_rows(&columns, width);
+        assert_eq!(
+            rows[0],
+            format!("WEB-1 1/2{}{SEPARATOR}WEB-2", " ".repeat(19))
+        );
+        assert!(rows.iter().all(|r| r.chars().count() <= 60));
+        assert_eq!(fit("Landing page", 8), "Landing…");
+    }
+
+    #[test]
+    fn columns_that_dont_fit_are_hidden() {
+        let tasks: Vec<TaskResponse> = Status::ALL
+            .iter()
+            .filter(|s| **s != Status::ARCHIVE)
+            .enumerate()
+            .map(|(i, s)| task(&format!("WEB-{i}"), "EPIC", &format!("{s:?}"), &[]))
+            .collect();
+        let mut columns = columns(tasks, false);
+        assert_eq!(columns.len(), 6);
+        let (width, hidden) = layout(&mut columns, 60);
+        assert_eq!((columns.len(), hidden.len()), (4, 2));
+        assert_eq!(width, MIN_COLUMN_WIDTH);
+        assert!(card_rows(&columns, width)
+            .iter()
+            .all(|r| r.chars().count() <= 60));
+        // Narrower than one column
+        let (width, hidden) = layout(&mut columns, 8);
+        assert_eq!((columns.len(), hidden.len(), width), (1, 3, 8));
+    }
+}
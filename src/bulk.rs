@@ -0,0 +1,173 @@
+use clap::Args;
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::HashSet;
+use umsebenzi::filter::TaskFilter;
+use umsebenzi::response::TaskResponse;
+
+use crate::cli_error::{CliError, Result};
+use crate::description::read_description_file;
+use crate::output::{print_list, OutputFormat};
+use crate::service::{confirm, delete_confirmation, resolve_assignee, RequestType};
+use crate::task::{fetch_task, fetch_tasks};
+use crate::GlobalArgs;
+
+// Tasks selected besides the codes given as arguments
+#[derive(Args, Debug)]
+pub struct TaskSelection {
+    /// Read task codes from a file, one per line, use '-' for stdin
+    #[arg(long, value_name = "FILE")]
+    from_file: Option<String>,
+    /// Tasks matching a task list filter eg: 'project=WEB,status=5'
+    #[arg(long)]
+    filter: Option<TaskFilter>,
+    /// Don't ask for confirmation
+    #[arg(short, long)]
+    yes: bool,
+}
+
+#[derive(Serialize)]
+pub struct TaskResult {
+    pub code: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+// Selected tasks, codes that couldn't be fetched are reported by run_each
+pub struct Selected {
+    pub tasks: Vec<TaskResponse>,
+    failed: Vec<TaskResult>,
+}
+
+impl TaskSelection {
+    // A single code without a file or filter keeps the one task behaviour
+    pub fn is_bulk(&self, codes: &[String]) -> bool {
+        codes.len() != 1 || self.from_file.is_some() || self.filter.is_some()
+    }
+
+    // Every selected task, each listed once
+    pub fn tasks(&self, codes: &[String], global: &GlobalArgs) -> Result<Selected> {
+        let mut codes = codes.to_vec();
+        if let Some(path) = &self.from_file {
+            let text = read_description_file(path)?;
+            codes.extend(
+                text.lines()
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty() && !c.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+        let mut tasks = Vec::new();
+        let mut failed = Vec::new();
+        for code in codes {
+            match fetch_task(&code, global) {
+                Ok(task) => tasks.push(task),
+                Err(err) => {
+                    eprintln!("{}: not fetched, {err}", code.bold());
+                    failed.push(TaskResult {
+                        code,
+                        ok: false,
+                        error: Some(format!("not fetched, {err}")),
+                    });
+                }
+            }
+        }
+        if let Some(filter) = &self.filter {
+            let mut filter = filter.clone();
+            resolve_assignee(&mut filter, global, None)?;
+            tasks.extend(fetch_tasks(&filter, global)?);
+        }
+        let mut seen = HashSet::new();
+        tasks.retain(|t| seen.insert(t.code.clone()));
+        if tasks.is_empty() && failed.is_empty() {
+            return Err(CliError::input("No tasks selected"));
+        }
+        Ok(Selected { tasks, failed })
+    }
+
+    // The confirmation of a single task, skipped with --yes like the others
+    pub fn confirm_one(&self, code: &String) -> Result<bool> {
+        if self.yes {
+            return Ok(true);
+        }
+        delete_confirmation(code, RequestType::TASK)
+    }
+
+    // One confirmation listing every task
+    pub fn confirm(&self, tasks: &[TaskResponse], action: &str) -> Result<bool> {
+        // Nothing to confirm when every code failed to fetch
+        if self.yes || tasks.is_empty() {
+            return Ok(true);
+        }
+        if self.from_file.as_deref() == Some("-") {
+            return Err(CliError::input(
+                "Task codes were read from stdin, use --yes to confirm",
+            ));
+        }
+        for t in tasks {
+            println!("  {} {} [{}]", t.code.bold(), t.title, t.status);
+        }
+        confirm(&format!("{} {} tasks?", action.red().bold(), tasks.len()))
+    }
+}
+
+// Run the action on every task, failures don't stop the remaining tasks
+pub fn run_each(
+    selected: Selected,
+    output: OutputFormat,
+    done: &str,
+    action: impl Fn(&TaskResponse) -> Result<()>,
+) -> Result<()> {
+    let mut results = selected.failed;
+    for task in &selected.tasks {
+        let result = match action(task) {
+            Ok(_) => {
+                if output.is_table() {
+                    println!("{}: {}", task.code.bold(), done.green());
+                }
+                TaskResult {
+                    code: task.code.clone(),
+                    ok: true,
+                    error: None,
+                }
+            }
+            Err(err) => {
+                eprintln!("{}: {err}", task.code.bold());
+                TaskResult {
+                    code: task.code.clone(),
+                    ok: false,
+                    error: Some(err.to_string()),
+                }
+            }
+        };
+        results.push(result);
+    }
+    if !output.is_table() {
+        print_list(&results, output)?;
+    }
+    let failed = results.iter().filter(|r| !r.ok).count();
+    if failed > 0 {
+        return Err(CliError::Partial(format!(
+            "{failed} of {} tasks failed",
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yes_skips_the_single_task_confirmation() {
+        let selection = TaskSelection {
+            from_file: None,
+            filter: None,
+            yes: true,
+        };
+        let codes = ["WEB-1".to_string()];
+        assert!(!selection.is_bulk(&codes));
+        assert!(selection.confirm_one(&codes[0]).unwrap());
+    }
+}
@@ -0,0 +1,328 @@
+use chrono::{DateTime, Local};
+use colored::Colorize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use umsebenzi::client::UmsebenziClient;
+use umsebenzi::filter::TaskFilter;
+use umsebenzi::response::{ProjectResponse, TaskResponse};
+
+use crate::cli_error::{CliError, Result};
+use crate::config::{config_dir, read_config_file_or_default, read_host, valid_profile_name};
+use crate::GlobalArgs;
+
+const CACHE_DIR: &str = "cache";
+const PROJECTS_FILE: &str = "projects.json";
+const TASKS_FILE: &str = "tasks.json";
+const USER_FILE: &str = "user.json";
+
+// Item as last fetched from the API
+#[derive(Serialize, Deserialize)]
+pub struct Cached<T> {
+    pub fetched_at: DateTime<Local>,
+    pub item: T,
+}
+
+// Projects and tasks fetched from one API host with the token of one profile,
+// read instead of the API with --offline. Writing is best effort, a failure
+// never stops a command
+pub struct Cache {
+    dir: PathBuf,
+}
+
+// Hosts become directory names, eg: http://localhost:8000/api/v1 to
+// http_localhost_8000_api_v1
+fn dir_name(host: &str) -> String {
+    host.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>()
+        .split('_')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+// Profiles of one host can hold the tokens of different users, each gets its
+// own directory under the host eg: http_localhost_8000/work
+fn dir_path(host: &str, profile: &str) -> PathBuf {
+    let profile = if valid_profile_name(profile) {
+        profile.to_string()
+    } else {
+        dir_name(profile)
+    };
+    PathBuf::from(dir_name(host)).join(profile)
+}
+
+// Replace cached items with the fetched ones, when complete is true the
+// fetched items are every item there is and the rest are dropped
+fn merge<T: Clone>(
+    cached: Vec<Cached<T>>,
+    fetched: &[T],
+    key: impl Fn(&T) -> String,
+    complete: bool,
+    now: DateTime<Local>,
+) -> Vec<Cached<T>> {
+    let mut items = if complete { Vec::new() } else { cached };
+    let mut index: HashMap<String, usize> = items
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (key(&c.item), i))
+        .collect();
+    for item in fetched {
+        let entry = Cached {
+            fetched_at: now,
+            item: item.clone(),
+        };
+        match index.get(&key(item)) {
+            Some(&i) => items[i] = entry,
+            None => {
+                index.insert(key(item), items.len());
+                items.push(entry);
+            }
+        }
+    }
+    items
+}
+
+// How long ago, rounded down to minutes, hours or days
+fn age(since: DateTime<Local>, now: DateTime<Local>) -> String {
+    let minutes = (now - since).num_minutes().max(0);
+    match minutes {
+        0 => "just now".to_string(),
+        m if m < 60 => format!("{m} minutes ago"),
+        m if m < 60 * 24 => format!("{} hours ago", m / 60),
+        m => format!("{} days ago", m / (60 * 24)),
+    }
+}
+
+// Say how old the shown data is, judged by its oldest item
+pub fn warn_stale<T>(items: &[Cached<T>], name: &str) {
+    let Some(oldest) = items.iter().map(|c| c.fetched_at).min() else {
+        return;
+    };
+    eprintln!(
+        "{}: {name} from the cache, fetched {} ({})",
+        "Offline".yellow().bold(),
+        oldest.format("%Y-%m-%d %H:%M"),
+        age(oldest, Local::now()),
+    );
+}
+
+impl Cache {
+    pub fn open(global: &GlobalArgs) -> Result<Cache> {
+        let host = read_host(global).map_err(|err| CliError::Config(err.to_string()))?;
+        let profile = read_config_file_or_default()?.active_profile_name(global.profile.as_deref());
+        Ok(Cache {
+            dir: config_dir()?
+                .join(CACHE_DIR)
+                .join(dir_path(&host, &profile)),
+        })
+    }
+
+    // Path of a file kept next to the cache
+    pub fn path(&self, file: &str) -> PathBuf {
+        self.dir.join(file)
+    }
+
+    fn read<T: DeserializeOwned>(&self, file: &str) -> Option<T> {
+        let text = fs::read_to_string(self.dir.join(file)).ok()?;
+        serde_json::from_str(&text).ok()
+    }
+
+    fn write<T: Serialize>(&self, file: &str, value: &T) {
+        let Ok(text) = serde_json::to_string(value) else {
+            return;
+        };
+        let _ = fs::create_dir_all(&self.dir).and_then(|_| fs::write(self.dir.join(file), text));
+    }
+
+    fn read_all<T: DeserializeOwned>(&self, file: &str, name: &str) -> Result<Vec<Cached<T>>> {
+        match self.read(file) {
+            Some(items) => Ok(items),
+            None => Err(CliError::Offline(format!(
+                "no cached {name}, run the command once without --offline"
+            ))),
+        }
+    }
+
+    pub fn projects(&self) -> Result<Vec<Cached<ProjectResponse>>> {
+        self.read_all(PROJECTS_FILE, "projects")
+    }
+
+    pub fn tasks(&self) -> Result<Vec<Cached<TaskResponse>>> {
+        self.read_all(TASKS_FILE, "tasks")
+    }
+
+    pub fn save_projects(&self, projects: &[ProjectResponse], complete: bool) {
+        let cached = self.read(PROJECTS_FILE).unwrap_or_default();
+        let items = merge(
+            cached,
+            projects,
+            |p| p.id.to_string(),
+            complete,
+            Local::now(),
+        );
+        self.write(PROJECTS_FILE, &items);
+    }
+
+    pub fn save_tasks(&self, tasks: &[TaskResponse], complete: bool) {
+        let cached = self.read(TASKS_FILE).unwrap_or_default();
+        let items = merge(cached, tasks, |t| t.code.clone(), complete, Local::now());
+        self.write(TASKS_FILE, &items);
+    }
+
+    // The task as it is after a write, taken from the response when it is a
+    // full task, otherwise fetched again
+    pub fn save_written_task(&self, client: &UmsebenziClient, code: &str, response: Option<Value>) {
+        let task = match response.and_then(|r| serde_json::from_value(r).ok()) {
+            Some(task) => task,
+            None => match client.get_task(code) {
+                Ok(task) => task,
+                Err(_) => return,
+            },
+        };
+        self.save_tasks(std::slice::from_ref(&task), false);
+    }
+
+    // The project as it is after a write, like save_written_task
+    pub fn save_written_project(
+        &self,
+        client: &UmsebenziClient,
+        id: &str,
+        response: Option<Value>,
+    ) {
+        let project = match response.and_then(|r| serde_json::from_value(r).ok()) {
+            Some(project) => project,
+            None => match client.get_project(id) {
+                Ok(project) => project,
+                Err(_) => return,
+            },
+        };
+        self.save_projects(std::slice::from_ref(&project), false);
+    }
+
+    pub fn remove_project(&self, id: &str) {
+        if let Some(mut items) = self.read::<Vec<Cached<ProjectResponse>>>(PROJECTS_FILE) {
+            items.retain(|c| c.item.id.to_string() != id);
+            self.write(PROJECTS_FILE, &items);
+        }
+    }
+
+    pub fn remove_task(&self, code: &str) {
+        if let Some(mut items) = self.read::<Vec<Cached<TaskResponse>>>(TASKS_FILE) {
+            items.retain(|c| c.item.code != code);
+            self.write(TASKS_FILE, &items);
+        }
+    }
+
+    // ID of the user the token belongs to, as last returned by /me
+    pub fn user(&self) -> Option<String> {
+        self.read(USER_FILE)
+    }
+
+    pub fn save_user(&self, id: &str) {
+        self.write(USER_FILE, &id);
+    }
+}
+
+// Cache of the selected host, None when it can't be located. Used by online
+// commands that keep the cache up to date
+pub fn cache(global: &GlobalArgs) -> Option<Cache> {
+    Cache::open(global).ok()
+}
+
+pub fn offline_projects(global: &GlobalArgs) -> Result<Vec<ProjectResponse>> {
+    let items = Cache::open(global)?.projects()?;
+    warn_stale(&items, "projects");
+    Ok(items.into_iter().map(|c| c.item).collect())
+}
+
+// Project by ID or code
+pub fn offline_project(global: &GlobalArgs, id: &str) -> Result<ProjectResponse> {
+    let items = Cache::open(global)?.projects()?;
+    let found = items
+        .into_iter()
+        .find(|c| c.item.id.to_string() == id || c.item.code.eq_ignore_ascii_case(id));
+    match found {
+        Some(c) => {
+            warn_stale(std::slice::from_ref(&c), "projects");
+            Ok(c.item)
+        }
+        None => Err(CliError::Offline(format!("project {id} is not cached"))),
+    }
+}
+
+pub fn offline_tasks(global: &GlobalArgs, filter: &TaskFilter) -> Result<Vec<TaskResponse>> {
+    let mut items = Cache::open(global)?.tasks()?;
+    items.retain(|c| filter.matches(&c.item));
+    warn_stale(&items, "tasks");
+    Ok(items.into_iter().map(|c| c.item).collect())
+}
+
+pub fn offline_task(global: &GlobalArgs, code: &str) -> Result<TaskResponse> {
+    let items = Cache::open(global)?.tasks()?;
+    let found = items
+        .into_iter()
+        .find(|c| c.item.code.eq_ignore_ascii_case(code));
+    match found {
+        Some(c) => {
+            warn_stale(std::slice::from_ref(&c), "tasks");
+            Ok(c.item)
+        }
+        None => Err(CliError::Offline(format!("task {code} is not cached"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(hour: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn fetched_items_replace_cached() {
+        let cached = vec![
+            Cached {
+                fetched_at: at(1),
+                item: "a".to_string(),
+            },
+            Cached {
+                fetched_at: at(1),
+                item: "b".to_string(),
+            },
+        ];
+        let fetched = ["b".to_string(), "c".to_string()];
+        let items = merge(cached, &fetched, |s| s.clone(), false, at(2));
+        let kept: Vec<(&str, DateTime<Local>)> = items
+            .iter()
+            .map(|c| (c.item.as_str(), c.fetched_at))
+            .collect();
+        assert_eq!(kept, vec![("a", at(1)), ("b", at(2)), ("c", at(2))]);
+        let items = merge(items, &fetched, |s| s.clone(), true, at(3));
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn host_and_age() {
+        assert_eq!(
+            dir_name("http://localhost:8000/api/v1"),
+            "http_localhost_8000_api_v1"
+        );
+        assert_ne!(
+            dir_path("http://localhost:8000", "work"),
+            dir_path("http://localhost:8000", "home")
+        );
+        assert_eq!(
+            dir_path("http://localhost:8000", "../x"),
+            PathBuf::from("http_localhost_8000/x")
+        );
+        assert_eq!(age(at(1), at(1)), "just now");
+        assert_eq!(age(at(1), at(4)), "3 hours ago");
+    }
+}
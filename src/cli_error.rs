@@ -0,0 +1,76 @@
+use colored::Colorize;
+use std::fmt;
+use std::io;
+use umsebenzi::error::ApiError;
+
+// Exit codes, 2 matches clap's usage errors
+pub const EXIT_FAILURE: i32 = 1;
+pub const EXIT_INPUT: i32 = 2;
+pub const EXIT_CONFIG: i32 = 3;
+pub const EXIT_NETWORK: i32 = 4;
+pub const EXIT_CLIENT_ERROR: i32 = 5;
+pub const EXIT_SERVER_ERROR: i32 = 6;
+pub const EXIT_PARSE: i32 = 7;
+
+pub type Result<T> = std::result::Result<T, CliError>;
+
+#[derive(Debug)]
+pub enum CliError {
+    // Config file missing or unreadable, or no usable profile
+    Config(String),
+    // Missing or invalid value given as a flag or at a prompt
+    Input(String),
+    Api(ApiError),
+    Io(io::Error),
+    // Some items of a command run on several items failed, the
+    // failures were already reported
+    Partial(String),
+    // The command needs the API or data missing from the cache with --offline
+    Offline(String),
+}
+
+impl CliError {
+    pub fn input(message: impl Into<String>) -> Self {
+        CliError::Input(message.into())
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Config(_) => EXIT_CONFIG,
+            CliError::Input(_) => EXIT_INPUT,
+            CliError::Api(ApiError::Network(_)) => EXIT_NETWORK,
+            CliError::Api(ApiError::Client { .. }) => EXIT_CLIENT_ERROR,
+            CliError::Api(ApiError::Server { .. }) => EXIT_SERVER_ERROR,
+            CliError::Api(ApiError::Parse(_)) => EXIT_PARSE,
+            CliError::Api(ApiError::InvalidToken) => EXIT_CONFIG,
+            CliError::Io(_) => EXIT_FAILURE,
+            CliError::Partial(_) => EXIT_FAILURE,
+            CliError::Offline(_) => EXIT_NETWORK,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Config(msg) => write!(f, "{}: {msg}", "Config error".red().bold()),
+            CliError::Input(msg) => write!(f, "{}", msg.red().bold()),
+            CliError::Api(err) => write!(f, "{}: {err}", "Error".red().bold()),
+            CliError::Io(err) => write!(f, "{}: {err}", "Error".red().bold()),
+            CliError::Partial(msg) => write!(f, "{}", msg.red().bold()),
+            CliError::Offline(msg) => write!(f, "{}: {msg}", "Offline".red().bold()),
+        }
+    }
+}
+
+impl From<ApiError> for CliError {
+    fn from(err: ApiError) -> Self {
+        CliError::Api(err)
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(err: io::Error) -> Self {
+        CliError::Io(err)
+    }
+}
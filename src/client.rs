@@ -0,0 +1,272 @@
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::enums::Status;
+use crate::error::{ApiError, ErrorResponse};
+use crate::filter::TaskFilter;
+use crate::page::{parse_page, Listing, Pages};
+use crate::request::{ProjectRequest, TaskRequest};
+use crate::response::{ProjectResponse, TaskResponse, User};
+
+pub const TASK_ENDPOINT: &str = "/tasks";
+pub const PROJECT_ENDPOINT: &str = "/projects";
+pub const ME_ENDPOINT: &str = "/me";
+
+// Decides how the body of a rejected request is parsed
+#[derive(Clone, Copy)]
+enum Resource {
+    Task,
+    Project,
+    User,
+}
+
+pub struct UmsebenziClient {
+    client: Client,
+    host: String,
+}
+
+fn get_headers(token: &str) -> Result<header::HeaderMap, ApiError> {
+    let mut auth = String::from("Token ");
+    auth.push_str(token);
+
+    let mut headers = header::HeaderMap::new();
+    headers.insert(
+        header::AUTHORIZATION,
+        header::HeaderValue::from_str(&auth).map_err(|_| ApiError::InvalidToken)?,
+    );
+    headers.insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    headers.insert(
+        header::ACCEPT,
+        header::HeaderValue::from_static("application/json"),
+    );
+    Ok(headers)
+}
+
+impl UmsebenziClient {
+    pub fn new(host: &str, token: &str) -> Result<Self, ApiError> {
+        let headers = get_headers(token)?;
+        let client = Client::builder().default_headers(headers).build()?;
+        Ok(UmsebenziClient {
+            client,
+            host: host.to_string(),
+        })
+    }
+
+    pub fn url(&self, endpoint: &str, instance: Option<&str>) -> String {
+        let mut api_url = String::new();
+        api_url.push_str(&self.host);
+        api_url.push_str(endpoint);
+        if let Some(id) = instance {
+            api_url.push('/');
+            api_url.push_str(id);
+        }
+        api_url
+    }
+
+    // Send the request, any non success status is returned as an error
+    fn send(&self, request: RequestBuilder, resource: Resource) -> Result<Response, ApiError> {
+        let resp = request.send()?;
+        let status = resp.status();
+        if status.is_success() {
+            return Ok(resp);
+        }
+        let body = resp.text().unwrap_or_default();
+        if status.is_client_error() {
+            let response = match resource {
+                Resource::Task => {
+                    serde_json::from_str(&body).map(|r| ErrorResponse::Task(Box::new(r)))
+                }
+                Resource::Project => {
+                    serde_json::from_str(&body).map(|r| ErrorResponse::Project(Box::new(r)))
+                }
+                Resource::User => {
+                    serde_json::from_str(&body).map(|r| ErrorResponse::Detail(Box::new(r)))
+                }
+            };
+            Err(ApiError::Client {
+                status: status.as_u16(),
+                response: response.unwrap_or(ErrorResponse::Text(body)),
+            })
+        } else {
+            Err(ApiError::Server {
+                status: status.as_u16(),
+                body,
+            })
+        }
+    }
+
+    fn json<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        resource: Resource,
+    ) -> Result<T, ApiError> {
+        let body = self.send(request, resource)?.text()?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    // Fetch pages of a list endpoint following next links, only items
+    // passing keep are returned
+    fn list<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+        pages: &Pages,
+        resource: Resource,
+        keep: impl Fn(&T) -> bool,
+    ) -> Result<Listing<T>, ApiError> {
+        let mut request = match pages.page {
+            Some(p) => request.query(&[("page", p)]),
+            None => request,
+        };
+        let mut listing = Listing {
+            items: Vec::new(),
+            count: None,
+            next: None,
+            filtered: false,
+        };
+        let mut fetched = 0;
+        loop {
+            let (items, count, next) = parse_page(self.json(request, resource)?)?;
+            fetched += 1;
+            let fetched_items = items.len();
+            let kept = listing.items.len();
+            listing.items.extend(items.into_iter().filter(|i| keep(i)));
+            listing.filtered |= listing.items.len() - kept < fetched_items;
+            listing.count = count;
+            if let Some(limit) = pages.limit {
+                if listing.items.len() >= limit {
+                    listing.items.truncate(limit);
+                    return Ok(listing);
+                }
+            }
+            let Some(next) = next else {
+                return Ok(listing);
+            };
+            if pages.page.is_some() {
+                return Ok(listing);
+            }
+            if pages.max_pages.is_some_and(|max| fetched >= max) {
+                listing.next = Some(next);
+                return Ok(listing);
+            }
+            request = self.client.get(next);
+        }
+    }
+
+    // Every task matching the filter, all pages are fetched
+    pub fn list_tasks(&self, filter: &TaskFilter) -> Result<Vec<TaskResponse>, ApiError> {
+        Ok(self.list_tasks_pages(filter, &Pages::default())?.items)
+    }
+
+    pub fn list_tasks_pages(
+        &self,
+        filter: &TaskFilter,
+        pages: &Pages,
+    ) -> Result<Listing<TaskResponse>, ApiError> {
+        let url = self.url(TASK_ENDPOINT, None);
+        self.list(
+            self.client.get(url).query(&filter.query_pairs()),
+            pages,
+            Resource::Task,
+            |t| filter.matches_listed(t),
+        )
+    }
+
+    pub fn get_task(&self, code: &str) -> Result<TaskResponse, ApiError> {
+        self.json(
+            self.client.get(self.url(TASK_ENDPOINT, Some(code))),
+            Resource::Task,
+        )
+    }
+
+    // Returns the task as sent back by the API
+    pub fn create_task(&self, task: &TaskRequest) -> Result<Value, ApiError> {
+        self.json(
+            self.client.post(self.url(TASK_ENDPOINT, None)).json(task),
+            Resource::Task,
+        )
+    }
+
+    pub fn update_task(&self, code: &str, task: &TaskRequest) -> Result<Value, ApiError> {
+        self.json(
+            self.client
+                .put(self.url(TASK_ENDPOINT, Some(code)))
+                .json(task),
+            Resource::Task,
+        )
+    }
+
+    pub fn update_status(&self, code: &str, status: Status) -> Result<(), ApiError> {
+        let mut data = HashMap::new();
+        data.insert("status", status.to_value());
+        let url = self.url(TASK_ENDPOINT, Some(code)) + "/status";
+        self.send(self.client.patch(url).json(&data), Resource::Task)?;
+        Ok(())
+    }
+
+    pub fn delete_task(&self, code: &str) -> Result<(), ApiError> {
+        self.send(
+            self.client.delete(self.url(TASK_ENDPOINT, Some(code))),
+            Resource::Task,
+        )?;
+        Ok(())
+    }
+
+    // Every project, all pages are fetched
+    pub fn list_projects(&self) -> Result<Vec<ProjectResponse>, ApiError> {
+        Ok(self.list_projects_pages(&Pages::default())?.items)
+    }
+
+    pub fn list_projects_pages(&self, pages: &Pages) -> Result<Listing<ProjectResponse>, ApiError> {
+        self.list(
+            self.client.get(self.url(PROJECT_ENDPOINT, None)),
+            pages,
+            Resource::Project,
+            |_| true,
+        )
+    }
+
+    pub fn get_project(&self, id: &str) -> Result<ProjectResponse, ApiError> {
+        self.json(
+            self.client.get(self.url(PROJECT_ENDPOINT, Some(id))),
+            Resource::Project,
+        )
+    }
+
+    // Returns the project as sent back by the API
+    pub fn create_project(&self, project: &ProjectRequest) -> Result<Value, ApiError> {
+        self.json(
+            self.client
+                .post(self.url(PROJECT_ENDPOINT, None))
+                .json(project),
+            Resource::Project,
+        )
+    }
+
+    pub fn update_project(&self, id: &str, project: &ProjectRequest) -> Result<Value, ApiError> {
+        self.json(
+            self.client
+                .put(self.url(PROJECT_ENDPOINT, Some(id)))
+                .json(project),
+            Resource::Project,
+        )
+    }
+
+    pub fn delete_project(&self, id: &str) -> Result<(), ApiError> {
+        self.send(
+            self.client.delete(self.url(PROJECT_ENDPOINT, Some(id))),
+            Resource::Project,
+        )?;
+        Ok(())
+    }
+
+    // The user the token belongs to
+    pub fn me(&self) -> Result<User, ApiError> {
+        self.json(self.client.get(self.url(ME_ENDPOINT, None)), Resource::User)
+    }
+}
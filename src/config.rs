@@ -1,27 +1,156 @@
 use anyhow::{anyhow, Result};
 use clap::{Parser, Subcommand};
+use cli_table::{print_stdout, Table, WithTitle};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::io::{self, Write};
 use std::{
     fs::{self, File},
     path,
 };
-use toml;
-use url;
 
-use crate::defaults::show_status_options;
+use crate::cli_error::{self, CliError};
+use crate::credentials::{mask_token, CredentialStore};
+use crate::defaults::{show_status_options, status_options};
+use crate::output::{print_item, print_list, OutputFormat};
+use crate::workflow::WorkflowRules;
+use crate::GlobalArgs;
 
 const CONFIG_DIR: &str = "XDG_CONFIG_HOME";
+const DEFAULT_PROFILE: &str = "default";
 
-#[derive(Deserialize, Serialize)]
+#[derive(Deserialize, Serialize, Clone)]
 pub struct Data {
     pub host: String,
-    pub credentials: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<String>,
+    #[serde(default, skip_serializing_if = "CredentialStore::is_plaintext")]
+    pub store: CredentialStore,
+    // Used for "me" instead of asking the API who the token belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+}
+
+// Host and token needed to make requests
+pub struct Credentials {
+    pub host: String,
+    pub token: String,
+}
+
+#[derive(Serialize)]
+struct ProfileView {
+    profile: String,
+    host: String,
+    store: CredentialStore,
+    token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<String>,
+}
+
+// Default columns of the list tables, --columns takes precedence
+#[derive(Deserialize, Serialize, Default, Clone)]
+pub struct ColumnConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<Vec<String>>,
+}
+
+impl ColumnConfig {
+    fn is_empty(&self) -> bool {
+        self.task.is_none() && self.project.is_none()
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+pub struct ConfigFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Data>,
+    #[serde(default, skip_serializing_if = "ColumnConfig::is_empty")]
+    pub columns: ColumnConfig,
+    // Allowed status changes per project code
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub workflow: BTreeMap<String, WorkflowRules>,
+    // Config files written before profiles existed only had these two keys,
+    // they are read as the "default" profile and never written back
+    #[serde(default, skip_serializing)]
+    host: Option<String>,
+    #[serde(default, skip_serializing)]
+    credentials: Option<String>,
+}
+
+impl ConfigFile {
+    fn from_toml(toml_str: &str) -> Result<Self> {
+        let mut file: ConfigFile = toml::from_str(toml_str)?;
+        if let (Some(host), Some(credentials)) = (file.host.take(), file.credentials.take()) {
+            file.profiles
+                .entry(DEFAULT_PROFILE.to_string())
+                .or_insert(Data {
+                    host,
+                    credentials: Some(credentials),
+                    store: CredentialStore::Plaintext,
+                    user_id: None,
+                });
+        }
+        Ok(file)
+    }
+
+    // Name of the profile used when none is given on the command line
+    pub fn active_profile_name(&self, name: Option<&str>) -> String {
+        if let Some(n) = name {
+            return n.to_string();
+        }
+        if let Some(n) = &self.default_profile {
+            return n.clone();
+        }
+        if self.profiles.len() == 1 {
+            if let Some(n) = self.profiles.keys().next() {
+                return n.clone();
+            }
+        }
+        DEFAULT_PROFILE.to_string()
+    }
+
+    pub fn profile(&self, name: Option<&str>) -> Result<(String, Data)> {
+        let profile = self.active_profile_name(name);
+        match self.profiles.get(&profile) {
+            Some(d) => Ok((profile, d.clone())),
+            None => Err(anyhow!("profile '{profile}' not found in umsebenzi.toml")),
+        }
+    }
+}
+
+#[derive(Serialize, Table)]
+struct ProfileRow {
+    #[table(title = "Profile")]
+    name: String,
+    #[table(title = "Host")]
+    host: String,
+    #[table(title = "Store")]
+    store: CredentialStore,
+    #[table(title = "Default")]
+    default: bool,
 }
 
 #[derive(Subcommand, Debug)]
 enum Config {
-    Add,
+    /// Add or replace the profile selected with --profile
+    Add {
+        /// Where the token is kept
+        #[arg(long, value_enum, default_value_t)]
+        store: CredentialStore,
+        /// Your user ID, looked up with the /me endpoint when not set
+        #[arg(long)]
+        user_id: Option<String>,
+    },
+    /// Set the profile used when --profile is not given
+    Use {
+        name: String,
+    },
+    /// Show every profile
+    List,
     TaskStatus,
 }
 
@@ -29,111 +158,245 @@ enum Config {
 pub struct ConfigArgs {
     #[command(subcommand)]
     command: Option<Config>,
+    /// Show the token instead of masking it
+    #[arg(long)]
+    reveal: bool,
 }
 
-pub fn run(args: ConfigArgs) {
+pub fn run(args: ConfigArgs, global: &GlobalArgs) -> cli_error::Result<()> {
+    let output = global.output;
     if let Some(v) = args.command {
         match v {
-            Config::Add => add(),
-            Config::TaskStatus => show_status_options(true),
+            Config::Add { store, user_id } => add(global.profile.as_deref(), store, user_id),
+            Config::Use { name } => use_profile(name),
+            Config::List => list(output),
+            Config::TaskStatus if output.is_table() => {
+                show_status_options(true);
+                Ok(())
+            }
+            Config::TaskStatus => print_list(&status_options(), output),
         }
     } else {
-        show();
+        show(global.profile.as_deref(), args.reveal, output)
     }
 }
 
-fn add() {
+fn config_error(err: anyhow::Error) -> CliError {
+    CliError::Config(err.to_string())
+}
+
+// Profile names end up in file names, eg: the encrypted <profile>.token
+pub fn valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// A missing file starts empty, an unreadable one is an error so it is never
+// written back without its other profiles and sections
+pub fn read_config_file_or_default() -> cli_error::Result<ConfigFile> {
+    if !config_dir()?.join("umsebenzi.toml").exists() {
+        return Ok(ConfigFile::default());
+    }
+    read_config_file().map_err(config_error)
+}
+
+fn add(
+    profile: Option<&str>,
+    store: CredentialStore,
+    user_id: Option<String>,
+) -> cli_error::Result<()> {
+    let mut file = read_config_file_or_default()?;
+    let name = file.active_profile_name(profile);
+    if !valid_profile_name(&name) {
+        return Err(CliError::input(
+            "Profile name may only contain letters, digits, '-' and '_'",
+        ));
+    }
     let mut host = "http://localhost:8000/api/v1";
 
     print!("Host [http://localhost:8000/api/v1]: ");
-    let _ = io::stdout().flush();
+    io::stdout().flush()?;
     let mut host_buf = String::new();
-    io::stdin()
-        .read_line(&mut host_buf)
-        .expect("host url expected");
+    io::stdin().read_line(&mut host_buf)?;
     if !host_buf.trim().is_empty() {
         match url::Url::parse(host_buf.trim()) {
             Ok(_) => host = host_buf.trim(),
-            Err(_) => {
-                eprintln!("Invalid url");
-                std::process::exit(1)
-            }
+            Err(_) => return Err(CliError::input("Invalid url")),
         }
     }
 
     print!("Token: ");
-    let _ = io::stdout().flush();
+    io::stdout().flush()?;
     let mut cred_buf = String::new();
-    io::stdin()
-        .read_line(&mut cred_buf)
-        .expect("token expected");
+    io::stdin().read_line(&mut cred_buf)?;
     if cred_buf.trim().is_empty() {
-        eprintln!("Token expected");
-        std::process::exit(1);
+        return Err(CliError::input("Token expected"));
     }
     let token = cred_buf.trim();
 
+    let credentials = store
+        .backend(config_dir()?)
+        .save(&name, token)
+        .map_err(|err| CliError::Config(format!("Unable to save token: {err}")))?;
     let data = Data {
         host: host.to_string(),
-        credentials: token.to_string(),
+        credentials,
+        store,
+        user_id,
     };
-    match write_toml_file(&data) {
-        Ok(_) => println!("Config file created"),
-        Err(err) => {
-            eprintln!("Can't write file: {}", err);
-            std::process::exit(1)
-        }
+    if file.default_profile.is_none() {
+        file.default_profile = Some(name.clone());
     }
+    file.profiles.insert(name.clone(), data);
+    write_toml_file(&file).map_err(config_error)?;
+    println!("Profile '{name}' saved");
+    Ok(())
 }
 
-fn show() {
-    let data = read_toml_file();
-    match data {
-        Ok(d) => {
-            println!("Host: {}", d.host);
-            println!("Token: {}", d.credentials);
-        }
-        Err(err) => {
-            eprintln!("Unable to read toml file: {err}");
-            std::process::exit(1);
+fn use_profile(name: String) -> cli_error::Result<()> {
+    let mut file = read_config_file_or_default()?;
+    if !file.profiles.contains_key(&name) {
+        return Err(CliError::Config(format!(
+            "Profile '{name}' not found, add it with: umsebenzi --profile {name} config add"
+        )));
+    }
+    file.default_profile = Some(name.clone());
+    write_toml_file(&file).map_err(config_error)?;
+    println!("Using profile '{name}'");
+    Ok(())
+}
+
+fn list(output: OutputFormat) -> cli_error::Result<()> {
+    let file = read_config_file().map_err(config_error)?;
+    let default = file.active_profile_name(None);
+    let rows: Vec<ProfileRow> = file
+        .profiles
+        .iter()
+        .map(|(name, data)| ProfileRow {
+            name: name.clone(),
+            host: data.host.clone(),
+            store: data.store,
+            default: *name == default,
+        })
+        .collect();
+    if output.is_table() {
+        print_stdout(rows.with_title())?;
+        Ok(())
+    } else {
+        print_list(&rows, output)
+    }
+}
+
+fn show(profile: Option<&str>, reveal: bool, output: OutputFormat) -> cli_error::Result<()> {
+    let (name, d) = read_config_file()
+        .and_then(|f| f.profile(profile))
+        .map_err(config_error)?;
+    let token = d
+        .store
+        .backend(config_dir()?)
+        .load(&name, d.credentials.as_deref())
+        .map_err(|err| CliError::Config(format!("Unable to read token: {err}")))?;
+    let token = if reveal { token } else { mask_token(&token) };
+    if output.is_table() {
+        println!("Profile: {}", name);
+        println!("Host: {}", d.host);
+        println!("Store: {}", d.store);
+        println!("Token: {}", token);
+        if let Some(id) = &d.user_id {
+            println!("User ID: {id}");
         }
+        Ok(())
+    } else {
+        let view = ProfileView {
+            profile: name,
+            host: d.host,
+            store: d.store,
+            token,
+            user_id: d.user_id,
+        };
+        print_item(&view, output)
     }
 }
 
+pub fn config_dir() -> cli_error::Result<path::PathBuf> {
+    config_file_path()
+        .map_err(|err| CliError::Config(format!("Can't create config directory: {err}")))
+}
+
+// $XDG_CONFIG_HOME/umsebenzi, falling back to ~/.config/umsebenzi
 fn config_file_path() -> Result<path::PathBuf> {
-    let config_dir = std::env::var(CONFIG_DIR)?;
-    let path = path::Path::new(&config_dir).join("umsebenzi");
+    let config_dir = match std::env::var(CONFIG_DIR) {
+        Ok(dir) if !dir.is_empty() => path::PathBuf::from(dir),
+        _ => {
+            let home = std::env::var("HOME")
+                .map_err(|_| anyhow!("neither {CONFIG_DIR} nor HOME is set"))?;
+            path::Path::new(&home).join(".config")
+        }
+    };
+    let path = config_dir.join("umsebenzi");
     if path.is_dir() {
         Ok(path)
     } else {
-        let _ = fs::create_dir(&path);
+        let _ = fs::create_dir_all(&path);
         Ok(path)
     }
 }
-pub fn read_toml_file() -> Result<Data> {
-    let directory = match config_file_path() {
-        Ok(path) => path,
-        Err(err) => {
-            eprintln!("Can't create config directory: {err}");
-            std::process::exit(1)
-        }
-    };
+pub fn read_config_file() -> Result<ConfigFile> {
+    let directory = config_file_path()?;
     let file_path = path::Path::new(&directory).join("umsebenzi.toml");
     if file_path.is_file() {
         let toml_str = fs::read_to_string(file_path)?;
-        let file: Data = toml::from_str(&toml_str)?;
-        return Ok(file);
+        return ConfigFile::from_toml(&toml_str);
     }
     Err(anyhow!("umsebenzi.toml file not found"))
 }
-fn write_toml_file(data: &Data) -> Result<()> {
-    let directory = match config_file_path() {
-        Ok(path) => path,
-        Err(err) => {
-            eprintln!("Can't create config directory: {err}");
-            std::process::exit(1)
-        }
+// Values are taken from, in order: --host/--token flags, UMSEBENZI_HOST/UMSEBENZI_TOKEN
+// env variables then the selected profile. The config file is not read when
+// both host and token are given.
+pub fn read_credentials(global: &GlobalArgs) -> Result<Credentials> {
+    if let (Some(host), Some(token)) = (&global.host, &global.token) {
+        return Ok(Credentials {
+            host: host.clone(),
+            token: token.clone(),
+        });
+    }
+    let (name, data) = read_config_file()?.profile(global.profile.as_deref())?;
+    let token = match &global.token {
+        Some(t) => t.clone(),
+        None => data
+            .store
+            .backend(config_file_path()?)
+            .load(&name, data.credentials.as_deref())?,
     };
+    Ok(Credentials {
+        host: global.host.clone().unwrap_or(data.host),
+        token,
+    })
+}
+// user_id of the selected profile, also used with --host and --token. The
+// token is not read so no passphrase is asked for
+pub fn read_user_id(global: &GlobalArgs) -> Option<String> {
+    read_config_file()
+        .ok()?
+        .profile(global.profile.as_deref())
+        .ok()?
+        .1
+        .user_id
+}
+// Host of the --host flag or the selected profile, the token is not read
+pub fn read_host(global: &GlobalArgs) -> Result<String> {
+    if let Some(host) = &global.host {
+        return Ok(host.clone());
+    }
+    Ok(read_config_file()?
+        .profile(global.profile.as_deref())?
+        .1
+        .host)
+}
+fn write_toml_file(data: &ConfigFile) -> Result<()> {
+    let directory = config_file_path()?;
     let file_path = path::Path::new(&directory).join("umsebenzi.toml");
 
     let toml_string = toml::to_string(data)?;
@@ -141,3 +404,63 @@ fn write_toml_file(data: &Data) -> Result<()> {
     file.write_all(toml_string.as_bytes())?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_file_is_default_profile() {
+        let file = ConfigFile::from_toml("host = \"http://a\"\ncredentials = \"t\"\n").unwrap();
+        let (name, data) = file.profile(None).unwrap();
+        assert_eq!(name, "default");
+        assert_eq!(data.host, "http://a");
+    }
+
+    #[test]
+    fn profile_names_are_checked() {
+        assert!(valid_profile_name("prod_2-eu"));
+        assert!(!valid_profile_name(""));
+        assert!(!valid_profile_name("../prod"));
+        assert!(!valid_profile_name("a b"));
+    }
+
+    #[test]
+    fn default_profile_is_used() {
+        let toml_str = r#"
+default_profile = "prod"
+
+[profiles.staging]
+host = "http://staging"
+credentials = "s"
+
+[profiles.prod]
+host = "http://prod"
+credentials = "p"
+"#;
+        let file = ConfigFile::from_toml(toml_str).unwrap();
+        assert_eq!(file.profile(None).unwrap().1.host, "http://prod");
+        assert_eq!(
+            file.profile(Some("staging")).unwrap().1.host,
+            "http://staging"
+        );
+        assert!(file.profile(Some("missing")).is_err());
+    }
+
+    #[test]
+    fn columns_are_kept() {
+        let toml_str = r#"
+[profiles.default]
+host = "http://a"
+credentials = "t"
+
+[columns]
+task = ["code", "title", "assigned_to"]
+"#;
+        let file = ConfigFile::from_toml(toml_str).unwrap();
+        assert_eq!(file.columns.task.as_ref().unwrap().len(), 3);
+        assert!(file.columns.project.is_none());
+        let written = toml::to_string(&file).unwrap();
+        assert!(written.contains("[columns]"));
+    }
+}
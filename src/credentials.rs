@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Result};
+use argon2::Argon2;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+const KEYRING_SERVICE: &str = "umsebenzi";
+const PASSPHRASE_ENV: &str = "UMSEBENZI_PASSPHRASE";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+// Where the api token of a profile is kept
+#[derive(ValueEnum, Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialStore {
+    // In umsebenzi.toml next to the host
+    #[default]
+    Plaintext,
+    // In <profile>.token, encrypted with a passphrase
+    Encrypted,
+    // In the OS secret store, Secret Service on linux
+    Keyring,
+}
+
+impl CredentialStore {
+    pub fn is_plaintext(&self) -> bool {
+        *self == CredentialStore::Plaintext
+    }
+
+    pub fn backend(self, config_dir: PathBuf) -> Box<dyn TokenStore> {
+        match self {
+            CredentialStore::Plaintext => Box::new(PlaintextStore),
+            CredentialStore::Encrypted => Box::new(EncryptedFileStore { dir: config_dir }),
+            CredentialStore::Keyring => Box::new(KeyringStore),
+        }
+    }
+}
+
+impl fmt::Display for CredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_possible_value() {
+            Some(v) => write!(f, "{}", v.get_name()),
+            None => Ok(()),
+        }
+    }
+}
+
+pub trait TokenStore {
+    // Returns the value to keep in the credentials key of the profile
+    fn save(&self, profile: &str, token: &str) -> Result<Option<String>>;
+    fn load(&self, profile: &str, credentials: Option<&str>) -> Result<String>;
+}
+
+struct PlaintextStore;
+
+impl TokenStore for PlaintextStore {
+    fn save(&self, _profile: &str, token: &str) -> Result<Option<String>> {
+        Ok(Some(token.to_string()))
+    }
+
+    fn load(&self, profile: &str, credentials: Option<&str>) -> Result<String> {
+        match credentials {
+            Some(c) => Ok(c.to_string()),
+            None => Err(anyhow!("profile '{profile}' has no credentials")),
+        }
+    }
+}
+
+struct EncryptedFileStore {
+    dir: PathBuf,
+}
+
+impl EncryptedFileStore {
+    fn path(&self, profile: &str) -> PathBuf {
+        self.dir.join(format!("{profile}.token"))
+    }
+}
+
+impl TokenStore for EncryptedFileStore {
+    fn save(&self, profile: &str, token: &str) -> Result<Option<String>> {
+        let passphrase = passphrase(true)?;
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options
+            .open(self.path(profile))?
+            .write_all(&encrypt(token, &passphrase)?)?;
+        Ok(None)
+    }
+
+    fn load(&self, profile: &str, _credentials: Option<&str>) -> Result<String> {
+        let data = fs::read(self.path(profile))
+            .map_err(|err| anyhow!("unable to read token file for '{profile}': {err}"))?;
+        decrypt(&data, &passphrase(false)?)
+    }
+}
+
+struct KeyringStore;
+
+impl TokenStore for KeyringStore {
+    fn save(&self, profile: &str, token: &str) -> Result<Option<String>> {
+        keyring::Entry::new(KEYRING_SERVICE, profile)?.set_password(token)?;
+        Ok(None)
+    }
+
+    fn load(&self, profile: &str, _credentials: Option<&str>) -> Result<String> {
+        let token = keyring::Entry::new(KEYRING_SERVICE, profile)?.get_password()?;
+        Ok(token)
+    }
+}
+
+fn passphrase(confirm: bool) -> Result<String> {
+    let passphrase = match std::env::var(PASSPHRASE_ENV) {
+        Ok(p) => p,
+        Err(_) => {
+            let passphrase = rpassword::prompt_password("Passphrase: ")?;
+            if confirm && rpassword::prompt_password("Confirm passphrase: ")? != passphrase {
+                return Err(anyhow!("passphrases do not match"));
+            }
+            passphrase
+        }
+    };
+    if passphrase.is_empty() {
+        return Err(anyhow!("passphrase expected"));
+    }
+    Ok(passphrase)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| anyhow!("unable to derive key: {err}"))?;
+    Ok(key)
+}
+
+// File layout is salt | nonce | ciphertext
+fn encrypt(token: &str, passphrase: &str) -> Result<Vec<u8>> {
+    let salt: [u8; SALT_LEN] = rand_bytes()?;
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, token.as_bytes())
+        .map_err(|_| anyhow!("unable to encrypt token"))?;
+
+    let mut data = salt.to_vec();
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+    Ok(data)
+}
+
+fn decrypt(data: &[u8], passphrase: &str) -> Result<String> {
+    if data.len() <= SALT_LEN + NONCE_LEN {
+        return Err(anyhow!("token file is corrupt"));
+    }
+    let (salt, rest) = data.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, salt)?);
+    let token = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("wrong passphrase or corrupt token file"))?;
+    Ok(String::from_utf8(token)?)
+}
+
+fn rand_bytes<const N: usize>() -> Result<[u8; N]> {
+    use chacha20poly1305::aead::rand_core::RngCore;
+    let mut buf = [0u8; N];
+    OsRng
+        .try_fill_bytes(&mut buf)
+        .map_err(|err| anyhow!("unable to generate salt: {err}"))?;
+    Ok(buf)
+}
+
+// Only the last 4 characters of a token are shown
+pub fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 8 {
+        return "*".repeat(8);
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}{tail}", "*".repeat(8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_token_round_trip() {
+        let data = encrypt("secret-token", "hunter2").unwrap();
+        assert_eq!(decrypt(&data, "hunter2").unwrap(), "secret-token");
+        assert!(decrypt(&data, "wrong").is_err());
+    }
+
+    #[test]
+    fn token_is_masked() {
+        assert_eq!(mask_token("abc"), "********");
+        assert_eq!(mask_token("0123456789abcdef"), "********cdef");
+    }
+}
@@ -0,0 +1,177 @@
+use chrono::{Local, NaiveDate};
+use clap::Parser;
+use cli_table::{print_stdout, Table, WithTitle};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use umsebenzi::enums::Status;
+use umsebenzi::filter::{is_overdue, TaskFilter};
+use umsebenzi::response::TaskResponse;
+
+use crate::cli_error::Result;
+use crate::output::print_item;
+use crate::service::{current_user, get_client};
+use crate::GlobalArgs;
+
+#[derive(Parser, Debug)]
+pub struct DashboardArgs {
+    /// Only tasks of this project code
+    #[arg(short, long)]
+    project: Option<String>,
+    /// Include archived tasks
+    #[arg(long)]
+    archived: bool,
+}
+
+#[derive(Serialize)]
+struct StatusGroup {
+    status: Status,
+    tasks: Vec<TaskResponse>,
+}
+
+#[derive(Serialize, Table)]
+struct ProjectCount {
+    #[table(title = "Project")]
+    project: String,
+    #[table(title = "Tasks")]
+    tasks: usize,
+    #[table(title = "Overdue")]
+    overdue: usize,
+}
+
+#[derive(Serialize)]
+struct Dashboard {
+    user_id: String,
+    // Codes of the tasks past their due date
+    overdue: Vec<String>,
+    statuses: Vec<StatusGroup>,
+    projects: Vec<ProjectCount>,
+}
+
+// Group the tasks by status in workflow order, statuses without tasks are left out
+fn summarise(user_id: String, tasks: Vec<TaskResponse>, today: NaiveDate) -> Dashboard {
+    let mut projects: BTreeMap<String, ProjectCount> = BTreeMap::new();
+    let mut overdue = Vec::new();
+    for task in &tasks {
+        let late = is_overdue(task, today);
+        if late {
+            overdue.push(task.code.clone());
+        }
+        let count = projects
+            .entry(task.project.code.clone())
+            .or_insert(ProjectCount {
+                project: task.project.code.clone(),
+                tasks: 0,
+                overdue: 0,
+            });
+        count.tasks += 1;
+        count.overdue += usize::from(late);
+    }
+    let mut statuses: Vec<StatusGroup> = Status::ALL
+        .iter()
+        .map(|s| StatusGroup {
+            status: *s,
+            tasks: Vec::new(),
+        })
+        .collect();
+    for task in tasks {
+        statuses[task.status.position()].tasks.push(task);
+    }
+    statuses.retain(|g| !g.tasks.is_empty());
+    Dashboard {
+        user_id,
+        overdue,
+        statuses,
+        projects: projects.into_values().collect(),
+    }
+}
+
+pub fn run(args: DashboardArgs, global: &GlobalArgs) -> Result<()> {
+    let client = get_client(global)?;
+    let user_id = current_user(global, Some(&client))?;
+    let filter = TaskFilter {
+        project: args.project,
+        assignee: Some(user_id.clone()),
+        ..Default::default()
+    };
+    let mut tasks = client.list_tasks(&filter)?;
+    if !args.archived {
+        tasks.retain(|t| t.status != Status::ARCHIVE);
+    }
+    let dashboard = summarise(user_id, tasks, Local::now().date_naive());
+    if !global.output.is_table() {
+        return print_item(&dashboard, global.output);
+    }
+    if dashboard.statuses.is_empty() {
+        println!("No tasks assigned to you");
+        return Ok(());
+    }
+    for group in &dashboard.statuses {
+        println!(
+            "{} ({})",
+            group.status.to_string().green().bold(),
+            group.tasks.len()
+        );
+        for t in &group.tasks {
+            let due = match &t.due_date {
+                Some(d) if dashboard.overdue.contains(&t.code) => {
+                    format!("due {d} overdue").red().bold().to_string()
+                }
+                Some(d) => format!("due {d}"),
+                None => String::new(),
+            };
+            println!("  {:<10} {:<8} {} {due}", t.code, t.project.code, t.title);
+        }
+        println!();
+    }
+    if !dashboard.overdue.is_empty() {
+        println!(
+            "{}: {}",
+            "Overdue".red().bold(),
+            dashboard.overdue.join(", ")
+        );
+        println!();
+    }
+    print_stdout(dashboard.projects.with_title())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn task(code: &str, project: &str, status: &str, due: Option<&str>) -> TaskResponse {
+        fixtures::task(1)
+            .project(project)
+            .set("title", code)
+            .set("code", code)
+            .set("status", status)
+            .set("due_date", due)
+            .build()
+    }
+
+    #[test]
+    fn grouped_by_status_and_project() {
+        let tasks = vec![
+            task("WEB-1", "WEB", "REVIEW", Some("2026-02-01")),
+            task("APP-2", "APP", "TO_DO", Some("2026-02-01")),
+            task("WEB-3", "WEB", "TO_DO", None),
+            task("WEB-4", "WEB", "COMPLETE", Some("2026-02-01")),
+        ];
+        let d = summarise("1".to_string(), tasks, "2026-03-01".parse().unwrap());
+        let statuses: Vec<Status> = d.statuses.iter().map(|g| g.status).collect();
+        assert_eq!(
+            statuses,
+            vec![Status::TO_DO, Status::REVIEW, Status::COMPLETE]
+        );
+        assert_eq!(d.statuses[0].tasks.len(), 2);
+        assert_eq!(d.overdue, vec!["WEB-1", "APP-2"]);
+        let counts: Vec<(&str, usize, usize)> = d
+            .projects
+            .iter()
+            .map(|p| (p.project.as_str(), p.tasks, p.overdue))
+            .collect();
+        assert_eq!(counts, vec![("APP", 1, 1), ("WEB", 3, 1)]);
+    }
+}
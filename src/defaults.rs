@@ -1,8 +1,18 @@
+use serde::Serialize;
+
+use umsebenzi::enums::Status;
+
+#[derive(Serialize)]
+pub struct StatusOption {
+    value: i32,
+    name: Status,
+}
+
 pub fn show_issue_options() {
     println!();
     println!("Select task issue");
-    println!("1) Epic");
-    println!("2) Subtask");
+    println!("1) Epic [epic]");
+    println!("2) Subtask [subtask, sub]");
 }
 
 pub fn show_status_options(is_config: bool) {
@@ -12,11 +22,21 @@ pub fn show_status_options(is_config: bool) {
     } else {
         println!("Select task status");
     }
-    println!("1) Draft");
-    println!("2) Ready");
-    println!("3) Todo");
-    println!("4) In Progress");
-    println!("5) Review");
-    println!("6) Complete");
-    println!("7) Archive");
+    println!("1) Draft [draft]");
+    println!("2) Ready [ready]");
+    println!("3) Todo [todo, to-do]");
+    println!("4) In Progress [in-progress, wip]");
+    println!("5) Review [review]");
+    println!("6) Complete [complete, done]");
+    println!("7) Archive [archive]");
+}
+
+pub fn status_options() -> Vec<StatusOption> {
+    Status::ALL
+        .iter()
+        .map(|s| StatusOption {
+            value: s.to_value(),
+            name: *s,
+        })
+        .collect()
 }
@@ -1,10 +1,11 @@
-use colored::Colorize;
 use std::env::var;
 use std::fs;
 use std::fs::File;
-use std::io::Write;
+use std::io::{self, Read, Write};
 use std::process::Command;
 
+use crate::cli_error::{CliError, Result};
+
 static ENV_VARS: &[&str] = &["VISUAL", "EDITOR"];
 
 fn get_editor() -> Option<String> {
@@ -18,49 +19,36 @@ fn get_editor() -> Option<String> {
     None
 }
 
-pub fn text_editor(data: Option<String>) -> Option<String> {
-    if let Some(editor) = get_editor() {
-        let mut temp_path = std::env::temp_dir();
-        temp_path.push("project.txt");
+pub fn text_editor(data: Option<String>) -> Result<String> {
+    let Some(editor) = get_editor() else {
+        return Err(CliError::input(
+            "Unable to find default editor, ensure VISUAL or EDITOR env variable has been set",
+        ));
+    };
+    let mut temp_path = std::env::temp_dir();
+    temp_path.push("project.txt");
 
-        let mut file = match File::create(&temp_path) {
-            Ok(f) => f,
-            Err(err) => {
-                eprintln!("Unable to create temp file: {:?}", err);
-                std::process::exit(1);
-            }
-        };
-        if data.is_some() {
-            file.write_all(data.expect("fail getting").as_bytes())
-                .expect("uable to write description to file");
-        }
+    let mut file = File::create(&temp_path)?;
+    if let Some(d) = data {
+        file.write_all(d.as_bytes())?;
+    }
 
-        let status = match Command::new(editor).arg(&temp_path).status() {
-            Ok(r) => r,
-            Err(err) => {
-                eprintln!("Unable to edit file {:?}", err);
-                std::process::exit(1);
-            }
-        };
-        if status.success() {
-            let editable = match fs::read_to_string(&temp_path) {
-                Ok(contents) => contents,
-                Err(err) => {
-                    eprintln!("Unable to read file {:?}", err);
-                    std::process::exit(1);
-                }
-            };
-            fs::remove_file(&temp_path).expect("Unable to delete temp file");
-            return Some(editable);
-        } else {
-            eprintln!("Unable to save description");
-            std::process::exit(1);
-        }
+    let status = Command::new(editor).arg(&temp_path).status()?;
+    if !status.success() {
+        return Err(CliError::input("Unable to save description"));
+    }
+    let editable = fs::read_to_string(&temp_path)?;
+    fs::remove_file(&temp_path)?;
+    Ok(editable)
+}
+
+// Read a description from a file, '-' reads from stdin
+pub fn read_description_file(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut buf = String::new();
+        io::stdin().read_to_string(&mut buf)?;
+        Ok(buf)
     } else {
-        eprintln!(
-            "{}: Unable to find default editor, ensure VISUAL or EDITOR env variable has been set",
-            "Error".red().bold()
-        );
-        std::process::exit(1);
+        fs::read_to_string(path)
     }
 }
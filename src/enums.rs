@@ -1,15 +1,35 @@
 use anyhow::{anyhow, Result};
 use std::fmt;
+use std::str::FromStr;
 use serde::{self, Deserialize, Serialize};
 
 use colored::Colorize;
 
-#[derive(PartialEq, Clone)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Issue {
     EPIC,
     SUBTASK,
 }
 
+// Lowercase with '-' and spaces read as '_', eg: "In-Progress" is "in_progress"
+fn normalise(s: &str) -> String {
+    s.trim().to_lowercase().replace(['-', ' '], "_")
+}
+
+impl FromStr for Issue {
+    type Err = anyhow::Error;
+
+    // Number, name or alias eg: "2", "subtask" or "sub"
+    fn from_str(s: &str) -> Result<Self> {
+        match normalise(s).as_str() {
+            "1" | "epic" => Ok(Issue::EPIC),
+            "2" | "subtask" | "sub_task" | "sub" => Ok(Issue::SUBTASK),
+            _ => Err(anyhow!("Invalid issue '{s}', expected 1, 2, epic or subtask")),
+        }
+    }
+}
+
 impl Issue {
     pub fn to_value(&self) -> i32 {
         match *self {
@@ -17,13 +37,6 @@ impl Issue {
             Self::SUBTASK => 2,
         }
     }
-    pub fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "1" => Ok(Issue::EPIC),
-            "2" => Ok(Issue::SUBTASK),
-            _ => Err(anyhow!("Invalid issue")),
-        }
-    }
     // API returns string variant eg: "EPIC".
     // this is only used in the editing of tasks
     pub fn from_api_str(s: &str) -> Result<Self> {
@@ -35,7 +48,9 @@ impl Issue {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Variant names match the values returned by the API
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Status{
     DRAFT,
     READY,
@@ -61,8 +76,32 @@ impl fmt::Display for Status {
 }
 
 impl Status {
-    pub fn to_value(&self) -> i32 {
-        match *self {
+    // Statuses in workflow order
+    pub const ALL: [Status; 7] = [
+        Status::DRAFT,
+        Status::READY,
+        Status::TO_DO,
+        Status::IN_PROGRESS,
+        Status::REVIEW,
+        Status::COMPLETE,
+        Status::ARCHIVE,
+    ];
+
+    // Index in the workflow order
+    pub fn position(self) -> usize {
+        (self.to_value() - 1) as usize
+    }
+
+    pub fn next(self) -> Option<Status> {
+        Status::ALL.get(self.position() + 1).copied()
+    }
+
+    pub fn previous(self) -> Option<Status> {
+        self.position().checked_sub(1).map(|i| Status::ALL[i])
+    }
+
+    pub fn to_value(self) -> i32 {
+        match self {
             Self::DRAFT => 1,
             Self::READY => 2,
             Self::TO_DO => 3,
@@ -72,17 +111,24 @@ impl Status {
             Self::ARCHIVE => 7,
         }
     }
-    // number repesenting status that user will enter
-    pub fn from_str(s: &str) -> Result<Status> {
-        match s {
-            "1" => Ok(Status::DRAFT),
-            "2" => Ok(Status::READY),
-            "3" => Ok(Status::TO_DO),
-            "4" => Ok(Status::IN_PROGRESS),
-            "5" => Ok(Status::REVIEW),
-            "6" => Ok(Status::COMPLETE),
-            "7" => Ok(Status::ARCHIVE),
-            _ => Err(anyhow!("Invalid task status")),
+}
+
+impl FromStr for Status {
+    type Err = anyhow::Error;
+
+    // Number, name or alias eg: "4", "in-progress" or "wip"
+    fn from_str(s: &str) -> Result<Status> {
+        match normalise(s).as_str() {
+            "1" | "draft" => Ok(Status::DRAFT),
+            "2" | "ready" => Ok(Status::READY),
+            "3" | "to_do" | "todo" => Ok(Status::TO_DO),
+            "4" | "in_progress" | "inprogress" | "wip" => Ok(Status::IN_PROGRESS),
+            "5" | "review" => Ok(Status::REVIEW),
+            "6" | "complete" | "completed" | "done" => Ok(Status::COMPLETE),
+            "7" | "archive" | "archived" => Ok(Status::ARCHIVE),
+            _ => Err(anyhow!(
+                "Invalid task status '{s}', see `umsebenzi config task-status`"
+            )),
         }
     }
 }
@@ -111,6 +157,32 @@ mod tests {
         assert!(epic.is_ok());
     }
 
+    #[test]
+    fn issue_from_alias() {
+        assert!(Issue::from_str("Epic").unwrap() == Issue::EPIC);
+        assert!(Issue::from_str("sub").unwrap() == Issue::SUBTASK);
+    }
+
+    #[test]
+    fn status_from_name_and_alias() {
+        assert_eq!(Status::from_str("4").unwrap(), Status::IN_PROGRESS);
+        assert_eq!(Status::from_str("In-Progress").unwrap(), Status::IN_PROGRESS);
+        assert_eq!(Status::from_str("WIP").unwrap(), Status::IN_PROGRESS);
+        assert_eq!(Status::from_str("todo").unwrap(), Status::TO_DO);
+        assert_eq!(Status::from_str("to do").unwrap(), Status::TO_DO);
+        assert_eq!(Status::from_str("done").unwrap(), Status::COMPLETE);
+        assert!(Status::from_str("8").is_err());
+        assert!(Status::from_str("finished").is_err());
+    }
+
+    #[test]
+    fn status_order() {
+        assert_eq!(Status::DRAFT.next(), Some(Status::READY));
+        assert_eq!(Status::ARCHIVE.next(), None);
+        assert_eq!(Status::REVIEW.previous(), Some(Status::IN_PROGRESS));
+        assert_eq!(Status::DRAFT.previous(), None);
+    }
+
     #[test]
     fn issue_from_api_correct() {
         let epic = Issue::from_api_str("EPIC");
@@ -0,0 +1,190 @@
+use std::fmt;
+
+use crate::response::{
+    ClientErrorResponse, DetailErrorResponse, TaskErrorResponse, NON_FIELD_KEYS,
+};
+
+// Longest part of a non json body included in error messages
+const BODY_PREVIEW_LEN: usize = 200;
+
+// Body of a 4xx response
+#[derive(Debug)]
+pub enum ErrorResponse {
+    Task(Box<TaskErrorResponse>),
+    Project(Box<ClientErrorResponse>),
+    Detail(Box<DetailErrorResponse>),
+    // Body that isn't the expected json, eg: an html error page
+    Text(String),
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    // The request could not be sent or the response could not be read
+    Network(reqwest::Error),
+    // The API rejected the request, 4xx status
+    Client {
+        status: u16,
+        response: ErrorResponse,
+    },
+    // The API failed to handle the request, 5xx status
+    Server {
+        status: u16,
+        body: String,
+    },
+    // The response body was not the expected json
+    Parse(serde_json::Error),
+    InvalidToken,
+}
+
+impl ApiError {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ApiError::Client { status: 404, .. })
+    }
+
+    // Request fields the API rejected, each listed once
+    pub fn rejected_fields(&self) -> Vec<&'static str> {
+        let mut fields = Vec::new();
+        if let ApiError::Client { response, .. } = self {
+            for (field, _) in response.field_errors() {
+                if !NON_FIELD_KEYS.contains(&field) && !fields.contains(&field) {
+                    fields.push(field);
+                }
+            }
+        }
+        fields
+    }
+}
+
+fn preview(body: &str) -> String {
+    let body = body.trim();
+    if body.chars().count() > BODY_PREVIEW_LEN {
+        let short: String = body.chars().take(BODY_PREVIEW_LEN).collect();
+        format!("{short}...")
+    } else {
+        body.to_string()
+    }
+}
+
+impl ErrorResponse {
+    pub fn field_errors(&self) -> Vec<(&'static str, String)> {
+        match self {
+            ErrorResponse::Task(r) => r.field_errors(),
+            ErrorResponse::Project(r) => r.field_errors(),
+            ErrorResponse::Detail(r) => r.field_errors(),
+            ErrorResponse::Text(_) => Vec::new(),
+        }
+    }
+}
+
+// One "field: message" line per message, errors that aren't about a
+// single field only show the message
+impl fmt::Display for ErrorResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = match self {
+            ErrorResponse::Text(body) => vec![preview(body)],
+            _ => self
+                .field_errors()
+                .into_iter()
+                .map(|(field, message)| {
+                    if NON_FIELD_KEYS.contains(&field) {
+                        message
+                    } else {
+                        format!("{field}: {message}")
+                    }
+                })
+                .collect(),
+        };
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "  - {line}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Network(err) => write!(f, "request failed: {err}"),
+            ApiError::Client { status, response } => {
+                write!(f, "request rejected with status {status}\n{response}")
+            }
+            ApiError::Server { status, body } => {
+                write!(f, "server error with status {status}: {}", preview(body))
+            }
+            ApiError::Parse(err) => write!(f, "unable to parse response json: {err}"),
+            ApiError::InvalidToken => write!(f, "token contains invalid characters"),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ApiError::Network(err) => Some(err),
+            ApiError::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(err: reqwest::Error) -> Self {
+        ApiError::Network(err)
+    }
+}
+
+impl From<serde_json::Error> for ApiError {
+    fn from(err: serde_json::Error) -> Self {
+        ApiError::Parse(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_error_lists_fields() {
+        let response: TaskErrorResponse = serde_json::from_str(
+            r#"{"title": ["This field may not be blank."], "due_date": ["Wrong format.", "Use YYYY-MM-DD."]}"#,
+        )
+        .unwrap();
+        let err = ApiError::Client {
+            status: 400,
+            response: ErrorResponse::Task(Box::new(response)),
+        };
+        assert_eq!(
+            err.to_string(),
+            "request rejected with status 400\n  - title: This field may not be blank.\n  - due_date: Wrong format.\n  - due_date: Use YYYY-MM-DD."
+        );
+    }
+
+    #[test]
+    fn detail_is_shown_without_field() {
+        let response: ClientErrorResponse = serde_json::from_str(
+            r#"{"code": ["project with this code already exists."], "detail": "Not allowed."}"#,
+        )
+        .unwrap();
+        let err = ApiError::Client {
+            status: 400,
+            response: ErrorResponse::Project(Box::new(response)),
+        };
+        assert_eq!(
+            err.to_string(),
+            "request rejected with status 400\n  - code: project with this code already exists.\n  - Not allowed."
+        );
+        assert_eq!(err.rejected_fields(), vec!["code"]);
+    }
+
+    #[test]
+    fn long_bodies_are_shortened() {
+        let err = ApiError::Server {
+            status: 500,
+            body: "x".repeat(500),
+        };
+        assert!(err.to_string().ends_with("..."));
+    }
+}
@@ -0,0 +1,231 @@
+use chrono::Local;
+use clap::{Parser, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use umsebenzi::enums::Issue;
+use umsebenzi::filter::{subtask_progress, TaskFilter};
+use umsebenzi::response::{ProjectResponse, TaskResponse};
+
+use crate::cli_error::{CliError, Result};
+use crate::output::{write_item, write_list, OutputFormat};
+use crate::project::find_project;
+use crate::task::fetch_tasks;
+use crate::GlobalArgs;
+
+#[derive(Parser, Debug)]
+pub struct ExportArgs {
+    /// Project code
+    #[arg(short, long)]
+    project: String,
+    /// File format, taken from the --out extension when not given
+    #[arg(short, long, value_enum)]
+    format: Option<ExportFormat>,
+    /// File to write, use '-' for stdout
+    #[arg(short, long, value_name = "FILE")]
+    out: String,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum ExportFormat {
+    Csv,
+    Json,
+    /// Markdown report grouped by epic
+    Md,
+}
+
+#[derive(Serialize)]
+struct Export<'a> {
+    project: &'a ProjectResponse,
+    exported_at: String,
+    tasks: &'a [TaskResponse],
+}
+
+fn format_of(out: &str) -> Result<ExportFormat> {
+    let extension = Path::new(out)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    ExportFormat::from_str(extension, true).map_err(|_| {
+        CliError::input("Unable to tell the format from the file name, use --format csv|json|md")
+    })
+}
+
+fn is_subtask(task: &TaskResponse) -> bool {
+    Issue::from_api_str(&task.issue).ok() == Some(Issue::SUBTASK)
+}
+
+// Epics with their subtasks, then the subtasks whose epic isn't in the export
+fn by_epic(
+    tasks: &[TaskResponse],
+) -> (Vec<(&TaskResponse, Vec<&TaskResponse>)>, Vec<&TaskResponse>) {
+    let mut epics: Vec<(&TaskResponse, Vec<&TaskResponse>)> = tasks
+        .iter()
+        .filter(|t| !is_subtask(t))
+        .map(|t| (t, Vec::new()))
+        .collect();
+    let mut other = Vec::new();
+    for task in tasks.iter().filter(|t| is_subtask(t)) {
+        match epics.iter_mut().find(|(e, _)| Some(e.id) == task.parent) {
+            Some((_, subtasks)) => subtasks.push(task),
+            None => other.push(task),
+        }
+    }
+    (epics, other)
+}
+
+// Descriptions are quoted so their own markdown, eg: "#" lines, can't break
+// the report headings
+fn quote(text: &mut String, description: &str) {
+    let description = description.trim();
+    if description.is_empty() {
+        return;
+    }
+    for line in description.lines() {
+        text.push_str(format!("> {line}").trim_end());
+        text.push('\n');
+    }
+    text.push('\n');
+}
+
+fn markdown_task(text: &mut String, task: &TaskResponse, heading: &str) {
+    text.push_str(&format!("{heading} {} {}\n\n", task.code, task.title));
+    text.push_str(&format!("- Status: {:?}\n", task.status));
+    text.push_str(&format!("- Assignee: {}\n", task.assigned_to));
+    text.push_str(&format!(
+        "- Due: {}\n",
+        task.due_date.as_deref().unwrap_or("-")
+    ));
+    if let Some((done, all)) = subtask_progress(task).filter(|(_, all)| *all > 0) {
+        text.push_str(&format!("- Subtasks: {done} of {all} complete\n"));
+    }
+    text.push_str(&format!(
+        "- Created by {} on {}, modified {}\n\n",
+        task.created_by, task.created_at, task.modified_at
+    ));
+    quote(text, &task.description);
+}
+
+fn markdown(project: &ProjectResponse, tasks: &[TaskResponse], exported_at: &str) -> String {
+    let mut text = format!("# {} {}\n\n", project.code, project.title);
+    quote(&mut text, &project.description);
+    text.push_str(&format!(
+        "{} tasks, exported {exported_at}\n\n",
+        tasks.len()
+    ));
+    let (epics, other) = by_epic(tasks);
+    for (epic, subtasks) in epics {
+        markdown_task(&mut text, epic, "##");
+        for subtask in subtasks {
+            markdown_task(&mut text, subtask, "###");
+        }
+    }
+    if !other.is_empty() {
+        text.push_str("## Subtasks without an epic\n\n");
+        for subtask in other {
+            markdown_task(&mut text, subtask, "###");
+        }
+    }
+    format!("{}\n", text.trim_end())
+}
+
+fn write(out: &mut impl Write, format: ExportFormat, export: &Export) -> anyhow::Result<()> {
+    match format {
+        ExportFormat::Csv => write_list(out, export.tasks, OutputFormat::Csv),
+        ExportFormat::Json => write_item(out, export, OutputFormat::Json),
+        ExportFormat::Md => {
+            let text = markdown(export.project, export.tasks, &export.exported_at);
+            Ok(out.write_all(text.as_bytes())?)
+        }
+    }
+}
+
+pub fn run(args: ExportArgs, global: &GlobalArgs) -> Result<()> {
+    let format = match args.format {
+        Some(f) => f,
+        None => format_of(&args.out)?,
+    };
+    let project = find_project(&args.project, global)?;
+    let filter = TaskFilter {
+        project: Some(project.code.clone()),
+        ..Default::default()
+    };
+    let tasks = fetch_tasks(&filter, global)?;
+    let export = Export {
+        project: &project,
+        exported_at: Local::now().format("%Y-%m-%d %H:%M").to_string(),
+        tasks: &tasks,
+    };
+    let written = if args.out == "-" {
+        write(&mut io::stdout(), format, &export)
+    } else {
+        write(&mut File::create(&args.out)?, format, &export)
+    };
+    written.map_err(|err| {
+        CliError::Io(io::Error::other(format!(
+            "Unable to write {}: {err}",
+            args.out
+        )))
+    })?;
+    if args.out != "-" && global.output.is_table() {
+        println!(
+            "{} {} tasks to {}",
+            "Exported".green().bold(),
+            tasks.len(),
+            args.out
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn task(id: i32, issue: &str, parent: Option<i32>) -> TaskResponse {
+        fixtures::task(id)
+            .set("issue", issue)
+            .set("description", format!("About task {id}"))
+            .set("parent", parent)
+            .build()
+    }
+
+    #[test]
+    fn markdown_is_grouped_by_epic() {
+        let project: ProjectResponse = fixtures::project(1).build();
+        let mut heading = task(1, "EPIC", None);
+        heading.description = "# Not a heading\n\nSecond paragraph".to_string();
+        let tasks = vec![
+            heading,
+            task(2, "SUBTASK", Some(3)),
+            task(3, "EPIC", None),
+            task(4, "SUBTASK", Some(9)),
+        ];
+        let text = markdown(&project, &tasks, "2026-03-01 10:00");
+        let headings: Vec<&str> = text.lines().filter(|l| l.starts_with('#')).collect();
+        assert_eq!(
+            headings,
+            vec![
+                "# WEB Website",
+                "## WEB-1 Task 1",
+                "## WEB-3 Task 3",
+                "### WEB-2 Task 2",
+                "## Subtasks without an epic",
+                "### WEB-4 Task 4",
+            ]
+        );
+        assert!(text.contains("- Status: TO_DO\n- Assignee: sam@example.com\n- Due: -\n"));
+        assert!(text.contains("> About task 2\n"));
+        assert!(text.contains("> # Not a heading\n>\n> Second paragraph\n"));
+    }
+
+    #[test]
+    fn format_from_file_name() {
+        assert_eq!(format_of("web.md").unwrap(), ExportFormat::Md);
+        assert_eq!(format_of("out/WEB.CSV").unwrap(), ExportFormat::Csv);
+        assert!(format_of("web").is_err());
+    }
+}
@@ -0,0 +1,337 @@
+use anyhow::{anyhow, Result};
+use chrono::{Local, NaiveDate};
+use std::str::FromStr;
+
+use crate::enums::{Issue, Status};
+use crate::response::TaskResponse;
+
+// Query parameters for the task list endpoint. Servers that ignore a
+// parameter still return the task, so the fields are also checked by
+// matches_listed. Search is left to the server, it may match text that isn't
+// in the task response
+#[derive(Debug, Default, Clone)]
+pub struct TaskFilter {
+    // Project code
+    pub project: Option<String>,
+    // Any of these statuses, every status when empty
+    pub status: Vec<Status>,
+    pub issue: Option<Issue>,
+    // User ID, or "me" for the authenticated user
+    pub assignee: Option<String>,
+    // Parent task ID
+    pub parent: Option<i32>,
+    // Due strictly before this date
+    pub due_before: Option<NaiveDate>,
+    // Due strictly after this date
+    pub due_after: Option<NaiveDate>,
+    // Past its due date and not complete or archived
+    pub overdue: bool,
+    // User ID or email
+    pub created_by: Option<String>,
+    // Case insensitive text in the code, title or description
+    pub search: Option<String>,
+}
+
+// Value used for the authenticated user in the assignee filter
+pub const CURRENT_USER: &str = "me";
+
+// Due dates may come back as a date or a datetime
+pub fn due_date(task: &TaskResponse) -> Option<NaiveDate> {
+    let due = task.due_date.as_deref()?;
+    NaiveDate::parse_from_str(due.get(..10)?, "%Y-%m-%d").ok()
+}
+
+pub fn is_overdue(task: &TaskResponse, today: NaiveDate) -> bool {
+    let done = matches!(task.status, Status::COMPLETE | Status::ARCHIVE);
+    !done && due_date(task).is_some_and(|d| d < today)
+}
+
+// Complete or archived subtasks and the number of subtasks of an epic
+pub fn subtask_progress(task: &TaskResponse) -> Option<(usize, usize)> {
+    if Issue::from_api_str(&task.issue).ok()? != Issue::EPIC {
+        return None;
+    }
+    let subtasks = task.subtasks.as_ref()?;
+    let done = subtasks
+        .iter()
+        .filter(|s| {
+            matches!(
+                Status::from_str(&s.status),
+                Ok(Status::COMPLETE | Status::ARCHIVE)
+            )
+        })
+        .count();
+    Some((done, subtasks.len()))
+}
+
+impl TaskFilter {
+    pub fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(p) = &self.project {
+            pairs.push(("project", p.clone()));
+        }
+        // A single status filter on the server can't express several,
+        // those are only filtered client side
+        if let [s] = self.status.as_slice() {
+            pairs.push(("status", s.to_value().to_string()));
+        }
+        if let Some(i) = &self.issue {
+            pairs.push(("issue", i.to_value().to_string()));
+        }
+        if let Some(a) = &self.assignee {
+            pairs.push(("assigned_to", a.clone()));
+        }
+        if let Some(p) = self.parent {
+            pairs.push(("parent", p.to_string()));
+        }
+        if let Some(d) = self.due_before {
+            pairs.push(("due_before", d.to_string()));
+        }
+        if let Some(d) = self.due_after {
+            pairs.push(("due_after", d.to_string()));
+        }
+        if self.overdue {
+            pairs.push(("overdue", "true".to_string()));
+        }
+        if let Some(c) = &self.created_by {
+            pairs.push(("created_by", c.clone()));
+        }
+        if let Some(s) = &self.search {
+            pairs.push(("search", s.clone()));
+        }
+        pairs
+    }
+
+    // True when every task matches
+    pub fn is_empty(&self) -> bool {
+        self.status.is_empty() && self.query_pairs().is_empty()
+    }
+
+    // Every field including search, for tasks read from the cache
+    pub fn matches(&self, task: &TaskResponse) -> bool {
+        self.matches_on(task, Local::now().date_naive())
+    }
+
+    pub fn matches_on(&self, task: &TaskResponse, today: NaiveDate) -> bool {
+        self.matches_fields(task, today) && self.matches_search(task)
+    }
+
+    // Tasks returned by the server for this filter
+    pub fn matches_listed(&self, task: &TaskResponse) -> bool {
+        self.matches_fields(task, Local::now().date_naive())
+    }
+
+    fn matches_fields(&self, task: &TaskResponse, today: NaiveDate) -> bool {
+        if self
+            .project
+            .as_ref()
+            .is_some_and(|p| !p.eq_ignore_ascii_case(&task.project.code))
+        {
+            return false;
+        }
+        if !self.status.is_empty() && !self.status.contains(&task.status) {
+            return false;
+        }
+        if let Some(i) = &self.issue {
+            if Issue::from_api_str(&task.issue).ok().as_ref() != Some(i) {
+                return false;
+            }
+        }
+        // "me" can only be checked by the server
+        if let Some(a) = &self.assignee {
+            if a != CURRENT_USER && *a != task.assigned_to.id.to_string() {
+                return false;
+            }
+        }
+        if self.parent.is_some() && self.parent != task.parent {
+            return false;
+        }
+        let due = due_date(task);
+        if let Some(before) = self.due_before {
+            if due.is_none_or(|d| d >= before) {
+                return false;
+            }
+        }
+        if let Some(after) = self.due_after {
+            if due.is_none_or(|d| d <= after) {
+                return false;
+            }
+        }
+        if self.overdue && !is_overdue(task, today) {
+            return false;
+        }
+        if let Some(c) = &self.created_by {
+            let user = &task.created_by;
+            if *c != user.id.to_string() && !c.eq_ignore_ascii_case(&user.email) {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn matches_search(&self, task: &TaskResponse) -> bool {
+        let Some(s) = &self.search else {
+            return true;
+        };
+        let s = s.to_lowercase();
+        [&task.code, &task.title, &task.description]
+            .iter()
+            .any(|text| text.to_lowercase().contains(&s))
+    }
+}
+
+// Filter written as comma separated key=value pairs using the query parameter
+// names, eg: "project=WEB,status=todo|wip,overdue=true"
+impl FromStr for TaskFilter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut filter = TaskFilter::default();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(anyhow!("expected key=value, found '{pair}'"));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "project" => filter.project = Some(value.to_string()),
+                "status" => {
+                    for s in value.split('|') {
+                        filter.status.push(Status::from_str(s)?);
+                    }
+                }
+                "issue" => filter.issue = Some(Issue::from_str(value)?),
+                "assignee" | "assigned_to" => filter.assignee = Some(value.to_string()),
+                "parent" => filter.parent = Some(value.parse()?),
+                "due_before" => filter.due_before = Some(value.parse()?),
+                "due_after" => filter.due_after = Some(value.parse()?),
+                "overdue" => filter.overdue = value.parse()?,
+                "created_by" => filter.created_by = Some(value.to_string()),
+                "search" => filter.search = Some(value.to_string()),
+                other => return Err(anyhow!("unknown filter '{other}'")),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn task() -> TaskResponse {
+        fixtures::task(7)
+            .set("title", "Fix login form")
+            .set("issue", "SUBTASK")
+            .set("description", "Password field is cut off")
+            .set("status", "IN_PROGRESS")
+            .set("due_date", "2026-03-10")
+            .set("subtasks", None::<()>)
+            .assignee(2, "lee")
+            .set("parent", 3)
+            .build()
+    }
+
+    #[test]
+    fn epics_show_progress() {
+        let epic: TaskResponse = fixtures::task(1)
+            .subtasks(&["COMPLETE", "ARCHIVE", "DRAFT"])
+            .build();
+        assert_eq!(subtask_progress(&epic), Some((2, 3)));
+        assert_eq!(subtask_progress(&task()), None);
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn query_is_encoded() {
+        let filter = TaskFilter {
+            project: Some("WEB".to_string()),
+            status: vec![Status::IN_PROGRESS],
+            search: Some("login & signup".to_string()),
+            ..Default::default()
+        };
+        let url =
+            url::Url::parse_with_params("http://localhost/tasks", filter.query_pairs()).unwrap();
+        assert_eq!(
+            url.query(),
+            Some("project=WEB&status=4&search=login+%26+signup")
+        );
+    }
+
+    #[test]
+    fn filter_expression() {
+        let filter: TaskFilter = "project=WEB, status=5|done,issue=epic,overdue=true"
+            .parse()
+            .unwrap();
+        assert_eq!(filter.project.as_deref(), Some("WEB"));
+        assert_eq!(filter.status, vec![Status::REVIEW, Status::COMPLETE]);
+        assert_eq!(filter.issue, Some(Issue::EPIC));
+        assert!(filter.overdue);
+        assert!("project".parse::<TaskFilter>().is_err());
+        assert!("colour=red".parse::<TaskFilter>().is_err());
+    }
+
+    #[test]
+    fn several_statuses_are_not_sent() {
+        let filter = TaskFilter {
+            status: vec![Status::TO_DO, Status::IN_PROGRESS],
+            ..Default::default()
+        };
+        assert!(filter.query_pairs().is_empty());
+        assert!(filter.matches(&task()));
+    }
+
+    #[test]
+    fn fields_are_matched() {
+        let today = date("2026-04-01");
+        let t = task();
+        let matching = TaskFilter {
+            project: Some("web".to_string()),
+            issue: Some(Issue::SUBTASK),
+            assignee: Some("2".to_string()),
+            parent: Some(3),
+            due_before: Some(date("2026-03-11")),
+            overdue: true,
+            created_by: Some("SAM@example.com".to_string()),
+            search: Some("PASSWORD".to_string()),
+            ..Default::default()
+        };
+        assert!(matching.matches_on(&t, today));
+
+        let others = [
+            TaskFilter {
+                issue: Some(Issue::EPIC),
+                ..Default::default()
+            },
+            TaskFilter {
+                assignee: Some("1".to_string()),
+                ..Default::default()
+            },
+            TaskFilter {
+                due_after: Some(date("2026-03-10")),
+                ..Default::default()
+            },
+            TaskFilter {
+                search: Some("signup".to_string()),
+                ..Default::default()
+            },
+        ];
+        for filter in others {
+            assert!(!filter.matches_on(&t, today), "{filter:?}");
+        }
+        let not_yet_due = TaskFilter {
+            overdue: true,
+            ..Default::default()
+        };
+        assert!(!not_yet_due.matches_on(&t, date("2026-03-10")));
+
+        let search = TaskFilter {
+            search: Some("signup".to_string()),
+            ..Default::default()
+        };
+        assert!(search.matches_listed(&t));
+    }
+}
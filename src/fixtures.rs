@@ -0,0 +1,78 @@
+// Task and project responses for unit tests. Declared by both lib.rs and
+// main.rs so the tests of either crate can use it, not every helper is used
+// by both
+#![allow(dead_code)]
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+pub struct Fixture(Value);
+
+// An epic of the WEB project assigned to and created by sam, due_date and
+// parent are null and there are no subtasks
+pub fn task(id: i32) -> Fixture {
+    Fixture(json!({
+        "id": id,
+        "project": {"id": 1, "title": "Website", "code": "WEB", "created_at": "2026-01-01"},
+        "title": format!("Task {id}"),
+        "code": format!("WEB-{id}"),
+        "issue": "EPIC",
+        "description": "",
+        "created_by": {"id": 1, "username": "sam", "email": "sam@example.com"},
+        "status": "TO_DO",
+        "due_date": null,
+        "modified_at": "2026-01-02",
+        "subtasks": [],
+        "assigned_to": {"id": 1, "username": "sam", "email": "sam@example.com"},
+        "created_at": "2026-01-01",
+        "parent": null
+    }))
+}
+
+// The WEB project created by sam
+pub fn project(id: i32) -> Fixture {
+    Fixture(json!({
+        "id": id,
+        "created_by": {"id": 1, "username": "sam", "email": "sam@example.com"},
+        "title": "Website",
+        "description": "",
+        "code": "WEB",
+        "created_at": "2026-01-01",
+        "modified_at": "2026-01-01"
+    }))
+}
+
+impl Fixture {
+    pub fn set(mut self, field: &str, value: impl Serialize) -> Self {
+        self.0[field] = json!(value);
+        self
+    }
+
+    pub fn project(self, code: &str) -> Self {
+        self.set(
+            "project",
+            json!({"id": 1, "title": code, "code": code, "created_at": "2026-01-01"}),
+        )
+    }
+
+    pub fn assignee(self, id: i32, username: &str) -> Self {
+        self.set(
+            "assigned_to",
+            json!({"id": id, "username": username, "email": format!("{username}@example.com")}),
+        )
+    }
+
+    // One subtask per status
+    pub fn subtasks(self, statuses: &[&str]) -> Self {
+        let subtasks: Vec<_> = statuses
+            .iter()
+            .map(|s| json!({"title": "t", "code": "c", "status": s, "created_at": "2026-01-01"}))
+            .collect();
+        self.set("subtasks", subtasks)
+    }
+
+    pub fn build<T: DeserializeOwned>(self) -> T {
+        serde_json::from_value(self.0).unwrap()
+    }
+}
@@ -0,0 +1,548 @@
+use chrono::NaiveDate;
+use clap::{Parser, ValueEnum};
+use colored::Colorize;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use std::str::FromStr;
+use umsebenzi::client::UmsebenziClient;
+use umsebenzi::enums::{Issue, Status};
+use umsebenzi::error::ApiError;
+use umsebenzi::filter::{TaskFilter, CURRENT_USER};
+use umsebenzi::request::TaskRequest;
+use umsebenzi::response::TaskResponse;
+
+use crate::cli_error::{CliError, Result};
+use crate::description::read_description_file;
+use crate::mapper::{rows, Source};
+use crate::output::{flatten, print_list};
+use crate::project::find_project;
+use crate::service::{current_user, get_client};
+use crate::task::fetch_tasks;
+use crate::GlobalArgs;
+
+// Task fields a column can be mapped to, with the columns read by default
+const FIELDS: [(&str, &[&str]); 8] = [
+    ("id", &["id", "external_id"]),
+    ("title", &["title"]),
+    ("description", &["description"]),
+    ("status", &["status"]),
+    ("issue", &["issue"]),
+    ("due_date", &["due_date", "due"]),
+    (
+        "assignee",
+        &["assignee", "assigned_to_id", "assigned_to.id"],
+    ),
+    ("parent", &["parent", "parent_id"]),
+];
+
+#[derive(Parser, Debug)]
+pub struct ImportArgs {
+    /// CSV, JSON or YAML file of tasks, use '-' for stdin with --format
+    file: String,
+    /// Project code
+    #[arg(short, long)]
+    project: String,
+    /// File format, taken from the file extension when not given
+    #[arg(short, long, value_enum)]
+    format: Option<FileFormat>,
+    /// Read an issue export of another tracker
+    #[arg(long, value_enum, conflicts_with = "format")]
+    from: Option<Source>,
+    /// Column of a task field eg: 'title=Summary,due_date=Due Date'. Fields
+    /// are id, title, description, status, issue, due_date, assignee and parent
+    #[arg(long, value_name = "FIELD=COLUMN", value_delimiter = ',')]
+    map: Vec<String>,
+    /// User ID of the rows without an assignee, defaults to you
+    #[arg(long)]
+    assignee: Option<String>,
+    /// Check every row without creating tasks
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum FileFormat {
+    Csv,
+    Json,
+    Yaml,
+}
+
+// Columns and values of one row, nested json and yaml objects become dotted
+// columns eg: assigned_to.id
+pub type Row = Vec<(String, String)>;
+
+// Which column each field is read from
+pub struct Mapping {
+    columns: Vec<(&'static str, Vec<String>)>,
+}
+
+impl Mapping {
+    pub fn new(map: &[String]) -> Result<Mapping> {
+        let mut columns: Vec<(&'static str, Vec<String>)> = FIELDS
+            .iter()
+            .map(|(field, names)| (*field, names.iter().map(|n| n.to_string()).collect()))
+            .collect();
+        for pair in map {
+            let Some((field, column)) = pair.split_once('=') else {
+                return Err(CliError::input(format!(
+                    "Invalid --map '{pair}', expected FIELD=COLUMN"
+                )));
+            };
+            let Some(entry) = columns.iter_mut().find(|(f, _)| *f == field.trim()) else {
+                let fields: Vec<&str> = FIELDS.iter().map(|(f, _)| *f).collect();
+                return Err(CliError::input(format!(
+                    "Unknown field '{field}' in --map, expected one of {}",
+                    fields.join(", ")
+                )));
+            };
+            entry.1 = vec![column.trim().to_string()];
+        }
+        Ok(Mapping { columns })
+    }
+
+    // Value of the field in the row, empty values count as missing
+    fn get<'a>(&self, row: &'a Row, field: &str) -> Option<&'a str> {
+        let (_, names) = self.columns.iter().find(|(f, _)| *f == field)?;
+        names.iter().find_map(|name| {
+            row.iter()
+                .find(|(column, value)| {
+                    column.eq_ignore_ascii_case(name) && !value.trim().is_empty()
+                })
+                .map(|(_, value)| value.trim())
+        })
+    }
+}
+
+fn format_of(file: &str) -> Result<FileFormat> {
+    let extension = Path::new(file)
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default();
+    if extension.eq_ignore_ascii_case("yml") {
+        return Ok(FileFormat::Yaml);
+    }
+    FileFormat::from_str(extension, true).map_err(|_| {
+        CliError::input("Unable to tell the format from the file name, use --format csv|json|yaml")
+    })
+}
+
+fn invalid_file(err: impl std::fmt::Display) -> CliError {
+    CliError::input(format!("Unable to read the import file: {err}"))
+}
+
+pub fn csv_rows(text: &str) -> Result<Vec<Row>> {
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    let headers = reader.headers().map_err(invalid_file)?.clone();
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(invalid_file)?;
+        rows.push(
+            headers
+                .iter()
+                .zip(record.iter())
+                .map(|(h, v)| (h.trim().to_string(), v.to_string()))
+                .collect(),
+        );
+    }
+    Ok(rows)
+}
+
+// A list of objects, or an object with the list under "tasks" as written by export
+fn value_rows(value: Value) -> Result<Vec<Row>> {
+    let items = match value {
+        Value::Array(items) => items,
+        Value::Object(mut map) => match map.remove("tasks") {
+            Some(Value::Array(items)) => items,
+            _ => return Err(invalid_file("expected a list of tasks")),
+        },
+        _ => return Err(invalid_file("expected a list of tasks")),
+    };
+    items
+        .into_iter()
+        .map(|item| {
+            if !item.is_object() {
+                return Err(invalid_file("every task should be an object"));
+            }
+            let mut row = Vec::new();
+            flatten("", &item, &mut row);
+            Ok(row)
+        })
+        .collect()
+}
+
+fn read_rows(text: &str, format: FileFormat) -> Result<Vec<Row>> {
+    match format {
+        FileFormat::Csv => csv_rows(text),
+        FileFormat::Json => value_rows(serde_json::from_str(text).map_err(invalid_file)?),
+        FileFormat::Yaml => value_rows(serde_yaml::from_str(text).map_err(invalid_file)?),
+    }
+}
+
+// Task of a row before its parent is looked up
+#[derive(Debug)]
+struct Planned {
+    row: usize,
+    id: Option<String>,
+    parent: Option<String>,
+    request: TaskRequest,
+}
+
+fn plan(
+    number: usize,
+    row: &Row,
+    mapping: &Mapping,
+    project_id: i32,
+    assignee: &Option<String>,
+) -> std::result::Result<Planned, String> {
+    let title = mapping.get(row, "title").ok_or("title is missing")?;
+    let status = match mapping.get(row, "status") {
+        Some(s) => Status::from_str(s).map_err(|err| format!("status: {err}"))?,
+        None => Status::DRAFT,
+    };
+    let parent = mapping.get(row, "parent").map(str::to_string);
+    let issue = match mapping.get(row, "issue") {
+        Some(i) => Issue::from_str(i).map_err(|err| format!("issue: {err}"))?,
+        None if parent.is_some() => Issue::SUBTASK,
+        None => Issue::EPIC,
+    };
+    if issue == Issue::SUBTASK && parent.is_none() {
+        return Err("parent is missing for a subtask".to_string());
+    }
+    // Dates with a time keep only the date eg: 2026-03-01T00:00:00Z
+    let due_date = match mapping.get(row, "due_date") {
+        Some(d) => {
+            let date = d.get(..10).unwrap_or(d);
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("due_date: '{d}' is not YYYY-MM-DD"))?;
+            Some(date.to_string())
+        }
+        None => None,
+    };
+    let assigned_to_id = match mapping.get(row, "assignee") {
+        Some(a) => a.to_string(),
+        None => assignee.clone().ok_or("assignee is missing")?,
+    };
+    Ok(Planned {
+        row: number,
+        id: mapping.get(row, "id").map(str::to_string),
+        parent: if issue == Issue::SUBTASK {
+            parent
+        } else {
+            None
+        },
+        request: TaskRequest {
+            project_id,
+            title: title.to_string(),
+            description: mapping
+                .get(row, "description")
+                .unwrap_or_default()
+                .to_string(),
+            status: status.to_value(),
+            issue: issue.to_value(),
+            due_date,
+            assigned_to_id,
+            parent_id: None,
+        },
+    })
+}
+
+// Task a subtask can be put under
+struct Parent {
+    // External ID from the file, or the code of an epic already in the project
+    id: Option<String>,
+    title: String,
+    // None for epics of a dry run, or when the create response has no ID
+    task_id: Option<i32>,
+    // Already in the project before the import
+    existing: bool,
+}
+
+// Looked up by ID first so titles used twice can still be told apart. Epics
+// of the file come before the epics already in the project
+fn find_parent(parents: &[Parent], reference: &str) -> Option<usize> {
+    let find = |matches: &dyn Fn(&Parent) -> bool| {
+        parents
+            .iter()
+            .position(|p| !p.existing && matches(p))
+            .or_else(|| parents.iter().position(|p| p.existing && matches(p)))
+    };
+    find(&|p| {
+        p.id.as_deref()
+            .is_some_and(|id| id.eq_ignore_ascii_case(reference))
+    })
+    .or_else(|| find(&|p| p.title.eq_ignore_ascii_case(reference)))
+}
+
+fn is_epic(task: &TaskResponse) -> bool {
+    Issue::from_api_str(&task.issue).is_ok_and(|i| i == Issue::EPIC)
+}
+
+// Fill in the IDs of created epics from the epics of the project, matched by
+// title among the epics that aren't parents yet
+fn look_up_ids(
+    client: &UmsebenziClient,
+    filter: &TaskFilter,
+    parents: &mut [Parent],
+) -> std::result::Result<(), ApiError> {
+    let tasks = client.list_tasks(filter)?;
+    for i in 0..parents.len() {
+        if parents[i].task_id.is_some() {
+            continue;
+        }
+        let found = tasks.iter().find(|t| {
+            is_epic(t)
+                && t.title == parents[i].title
+                && !parents.iter().any(|p| p.task_id == Some(t.id))
+        });
+        parents[i].task_id = found.map(|t| t.id);
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct RowResult {
+    row: usize,
+    title: String,
+    code: Option<String>,
+    ok: bool,
+    error: Option<String>,
+}
+
+// Field errors on one line eg: "title: too long; due_date: invalid"
+fn api_error(err: ApiError) -> String {
+    match &err {
+        ApiError::Client { response, .. } if !response.field_errors().is_empty() => response
+            .field_errors()
+            .into_iter()
+            .map(|(field, message)| format!("{field}: {message}"))
+            .collect::<Vec<_>>()
+            .join("; "),
+        _ => err.to_string(),
+    }
+}
+
+// Create the tasks of the rows, epics first so subtasks can be put under them
+pub fn import_rows(
+    rows: &[Row],
+    mapping: &Mapping,
+    project_code: &str,
+    assignee: Option<String>,
+    dry_run: bool,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let project = find_project(project_code, global)?;
+    let assignee = match assignee {
+        Some(a) if a != CURRENT_USER => Some(a),
+        _ if rows.iter().all(|r| mapping.get(r, "assignee").is_some()) => None,
+        _ => Some(current_user(global, None)?),
+    };
+    let filter = TaskFilter {
+        project: Some(project.code.clone()),
+        ..Default::default()
+    };
+    let existing: Vec<TaskResponse> = fetch_tasks(&filter, global)?;
+    // Only epics can have subtasks
+    let mut parents: Vec<Parent> = existing
+        .iter()
+        .filter(|t| is_epic(t))
+        .map(|t| Parent {
+            id: Some(t.code.clone()),
+            title: t.title.clone(),
+            task_id: Some(t.id),
+            existing: true,
+        })
+        .collect();
+    let client = if dry_run {
+        None
+    } else {
+        Some(get_client(global)?)
+    };
+
+    let mut results = Vec::new();
+    let mut planned = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        match plan(i + 1, row, mapping, project.id, &assignee) {
+            Ok(p) => planned.push(p),
+            Err(error) => results.push(RowResult {
+                row: i + 1,
+                title: mapping.get(row, "title").unwrap_or_default().to_string(),
+                code: None,
+                ok: false,
+                error: Some(error),
+            }),
+        }
+    }
+    planned.sort_by_key(|p| p.parent.is_some());
+
+    let mut planned = planned.into_iter();
+    while let Some(mut p) = planned.next() {
+        let mut result = RowResult {
+            row: p.row,
+            title: p.request.title.clone(),
+            code: None,
+            ok: false,
+            error: None,
+        };
+        if let Some(reference) = &p.parent {
+            let Some(i) = find_parent(&parents, reference) else {
+                result.error = Some(format!("parent '{reference}' not found"));
+                results.push(result);
+                continue;
+            };
+            if let (Some(c), None) = (&client, parents[i].task_id) {
+                // Without the task list no later row can be placed either,
+                // they are reported as not imported
+                if let Err(err) = look_up_ids(c, &filter, &mut parents) {
+                    let error = api_error(err);
+                    result.error =
+                        Some(format!("ID of parent '{reference}' not looked up: {error}"));
+                    results.push(result);
+                    results.extend(planned.by_ref().map(|p| RowResult {
+                        row: p.row,
+                        title: p.request.title,
+                        code: None,
+                        ok: false,
+                        error: Some(format!("not imported: {error}")),
+                    }));
+                    break;
+                }
+                if parents[i].task_id.is_none() {
+                    result.error = Some(format!("ID of parent '{reference}' not found"));
+                    results.push(result);
+                    continue;
+                }
+            }
+            p.request.parent_id = parents[i].task_id;
+        }
+        let created = match &client {
+            // Dry runs don't know the ID of new epics
+            None => Ok(Value::Null),
+            Some(c) => c.create_task(&p.request).map_err(api_error),
+        };
+        match created {
+            Ok(value) => {
+                result.ok = true;
+                result.code = value["code"].as_str().map(str::to_string);
+                if p.parent.is_none() {
+                    parents.push(Parent {
+                        id: p.id.clone(),
+                        title: p.request.title.clone(),
+                        task_id: value["id"].as_i64().map(|id| id as i32),
+                        existing: false,
+                    });
+                }
+            }
+            Err(error) => result.error = Some(error),
+        }
+        results.push(result);
+    }
+    results.sort_by_key(|r| r.row);
+
+    if global.output.is_table() {
+        let done = if dry_run { "ok" } else { "created" };
+        for r in &results {
+            let name = format!("row {} '{}'", r.row, r.title);
+            match &r.error {
+                None => match &r.code {
+                    Some(code) => println!("{}: {} {code}", name.bold(), done.green()),
+                    None => println!("{}: {}", name.bold(), done.green()),
+                },
+                Some(error) => eprintln!("{}: {}", name.bold(), error.red()),
+            }
+        }
+    } else {
+        print_list(&results, global.output)?;
+    }
+    let failed = results.iter().filter(|r| !r.ok).count();
+    if failed > 0 {
+        return Err(CliError::Partial(format!(
+            "{failed} of {} rows failed",
+            results.len()
+        )));
+    }
+    Ok(())
+}
+
+pub fn run(args: ImportArgs, global: &GlobalArgs) -> Result<()> {
+    let rows = match (args.from, args.format) {
+        (Some(source), _) => rows(source, &read_description_file(&args.file)?)?,
+        (None, format) => {
+            let format = match format {
+                Some(f) => f,
+                None => format_of(&args.file)?,
+            };
+            read_rows(&read_description_file(&args.file)?, format)?
+        }
+    };
+    if rows.is_empty() {
+        return Err(CliError::input("No rows to import"));
+    }
+    let mapping = Mapping::new(&args.map)?;
+    import_rows(
+        &rows,
+        &mapping,
+        &args.project,
+        args.assignee,
+        args.dry_run,
+        global,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    #[test]
+    fn rows_are_mapped_to_requests() {
+        let text = "Summary,Status,Due,Epic\nLanding page,wip,2026-03-01T00:00:00Z,\nHero,done,,Landing page\nFooter,nope,,\n";
+        let rows = read_rows(text, FileFormat::Csv).unwrap();
+        let mapping =
+            Mapping::new(&["title=Summary".to_string(), "parent=Epic".to_string()]).unwrap();
+        let assignee = Some("1".to_string());
+        let epic = plan(1, &rows[0], &mapping, 7, &assignee).unwrap();
+        assert_eq!(epic.request.title, "Landing page");
+        assert_eq!(epic.request.status, Status::IN_PROGRESS.to_value());
+        assert_eq!(epic.request.due_date.as_deref(), Some("2026-03-01"));
+        assert_eq!(epic.request.issue, Issue::EPIC.to_value());
+        let subtask = plan(2, &rows[1], &mapping, 7, &assignee).unwrap();
+        assert_eq!(subtask.request.issue, Issue::SUBTASK.to_value());
+        assert_eq!(subtask.parent.as_deref(), Some("Landing page"));
+        let err = plan(3, &rows[2], &mapping, 7, &assignee).unwrap_err();
+        assert!(err.starts_with("status: Invalid task status 'nope'"));
+        assert!(Mapping::new(&["name=Summary".to_string()]).is_err());
+    }
+
+    #[test]
+    fn parents_by_id_then_title() {
+        let parents = vec![
+            Parent {
+                id: Some("WEB-1".to_string()),
+                title: "Landing".to_string(),
+                task_id: Some(1),
+                existing: true,
+            },
+            Parent {
+                id: Some("17".to_string()),
+                title: "Landing".to_string(),
+                task_id: Some(2),
+                existing: false,
+            },
+        ];
+        assert_eq!(find_parent(&parents, "17"), Some(1));
+        assert_eq!(find_parent(&parents, "web-1"), Some(0));
+        // The epic from the file over the one already in the project
+        assert_eq!(find_parent(&parents, "landing"), Some(1));
+        assert_eq!(find_parent(&parents, "Footer"), None);
+        assert!(is_epic(&fixtures::task(1).build()));
+        assert!(!is_epic(&fixtures::task(2).set("issue", "SUBTASK").build()));
+        let rows =
+            value_rows(serde_json::json!({"tasks": [{"title": "A", "assigned_to": {"id": 3}}]}))
+                .unwrap();
+        assert_eq!(
+            Mapping::new(&[]).unwrap().get(&rows[0], "assignee"),
+            Some("3")
+        );
+    }
+}
@@ -0,0 +1,24 @@
+//! Client for the django-umsebenzi API.
+//!
+//! ```no_run
+//! use umsebenzi::client::UmsebenziClient;
+//! use umsebenzi::filter::TaskFilter;
+//!
+//! let client = UmsebenziClient::new("http://localhost:8000/api/v1", "token").unwrap();
+//! let filter = TaskFilter {
+//!     project: Some("WEB".to_string()),
+//!     ..Default::default()
+//! };
+//! for task in client.list_tasks(&filter).unwrap() {
+//!     println!("{} {}", task.code, task.title);
+//! }
+//! ```
+pub mod client;
+pub mod enums;
+pub mod error;
+pub mod filter;
+#[cfg(test)]
+mod fixtures;
+pub mod page;
+pub mod request;
+pub mod response;
@@ -1,36 +1,103 @@
+mod board;
+mod bulk;
+mod cache;
+mod cli_error;
 mod config;
+mod credentials;
+mod dashboard;
 mod defaults;
 mod description;
-mod enums;
+mod export;
+#[cfg(test)]
+mod fixtures;
+mod import;
+mod mapper;
+mod output;
 mod project;
-mod request;
-mod response;
+mod prompt;
 mod service;
+mod sync;
+mod table;
 mod task;
+mod tree;
+mod tui;
+mod workflow;
 
-use clap::{Parser, Subcommand};
+use board::{run as b, BoardArgs};
+use clap::{Args, Parser, Subcommand};
 use config::{run as c, ConfigArgs};
+use dashboard::{run as d, DashboardArgs};
+use export::{run as e, ExportArgs};
+use import::{run as i, ImportArgs};
+use output::OutputFormat;
 use project::{run as p, ProjectArgs};
+use sync::{run as s, SyncArgs};
 use task::{run as t, TaskArgs};
+use tui::{run as u, TuiArgs};
 
 #[derive(Subcommand)]
 enum Command {
     Project(ProjectArgs),
-    Task(TaskArgs),
+    Task(Box<TaskArgs>),
     Config(ConfigArgs),
+    /// Your tasks grouped by status with counts per project
+    Dashboard(DashboardArgs),
+    /// Tasks in a column per status
+    Board(BoardArgs),
+    /// Browse and edit projects and tasks full screen
+    Tui(TuiArgs),
+    /// Send the task changes queued with --offline
+    Sync(SyncArgs),
+    /// Write the tasks of a project to a csv, json or markdown file
+    Export(ExportArgs),
+    /// Create the tasks of a csv, json or yaml file in a project
+    Import(ImportArgs),
+}
+
+// Options shared by every command
+#[derive(Args)]
+pub struct GlobalArgs {
+    /// Output format
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub output: OutputFormat,
+    /// Config profile, defaults to default_profile in umsebenzi.toml
+    #[arg(long, global = true, env = "UMSEBENZI_PROFILE")]
+    pub profile: Option<String>,
+    /// API host, overrides the profile host
+    #[arg(long, global = true, env = "UMSEBENZI_HOST")]
+    pub host: Option<String>,
+    /// API token, overrides the profile token
+    #[arg(long, global = true, env = "UMSEBENZI_TOKEN", hide_env_values = true)]
+    pub token: Option<String>,
+    /// Read projects and tasks from the local cache instead of the API, and
+    /// queue task changes for `sync`
+    #[arg(long, global = true)]
+    pub offline: bool,
 }
 
 #[derive(Parser)]
 struct Cli {
     #[command(subcommand)]
     command: Command,
+    #[command(flatten)]
+    global: GlobalArgs,
 }
 
 fn main() {
     let args = Cli::parse();
-    match args.command {
-        Command::Config(v) => c(v),
-        Command::Project(v) => p(v),
-        Command::Task(v) => t(v),
+    let result = match args.command {
+        Command::Config(v) => c(v, &args.global),
+        Command::Project(v) => p(v, &args.global),
+        Command::Task(v) => t(*v, &args.global),
+        Command::Dashboard(v) => d(v, &args.global),
+        Command::Board(v) => b(v, &args.global),
+        Command::Tui(v) => u(v, &args.global),
+        Command::Sync(v) => s(v, &args.global),
+        Command::Export(v) => e(v, &args.global),
+        Command::Import(v) => i(v, &args.global),
+    };
+    if let Err(err) = result {
+        eprintln!("{err}");
+        std::process::exit(err.exit_code());
     }
 }
@@ -0,0 +1,454 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use clap::ValueEnum;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::str::FromStr;
+use umsebenzi::enums::{Issue, Status};
+
+use crate::cli_error::{CliError, Result};
+use crate::import::{csv_rows, Row};
+
+// Label that makes an issue or card an epic
+const EPIC_LABEL: &str = "epic";
+
+// Export formats of other trackers
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum Source {
+    /// Issues json from the GitHub API or `gh issue list --json`
+    Github,
+    /// Issues csv exported from GitLab
+    Gitlab,
+    /// Issues csv exported from Jira
+    Jira,
+    /// Board json exported from Trello
+    Trello,
+}
+
+// Task read from another tracker, parent is the title of its epic
+#[derive(Debug)]
+struct Item {
+    id: String,
+    title: String,
+    description: String,
+    status: Status,
+    epic: bool,
+    due_date: Option<String>,
+    parent: Option<String>,
+}
+
+impl Item {
+    fn row(self) -> Row {
+        let issue = if self.epic || self.parent.is_none() {
+            Issue::EPIC
+        } else {
+            Issue::SUBTASK
+        };
+        let mut row = vec![
+            ("id".to_string(), self.id),
+            ("title".to_string(), self.title),
+            ("description".to_string(), self.description),
+            ("status".to_string(), format!("{:?}", self.status)),
+            ("issue".to_string(), issue.to_value().to_string()),
+        ];
+        if let Some(d) = self.due_date {
+            row.push(("due_date".to_string(), d));
+        }
+        if let (Some(p), Issue::SUBTASK) = (self.parent, issue) {
+            row.push(("parent".to_string(), p));
+        }
+        row
+    }
+}
+
+// Status of a workflow state, list or label name used by other trackers
+fn status_of(name: &str) -> Option<Status> {
+    if let Ok(status) = Status::from_str(name) {
+        return Some(status);
+    }
+    let name = name.trim().to_lowercase();
+    let status = match name.as_str() {
+        "backlog" | "icebox" | "ideas" => Status::DRAFT,
+        "selected for development" | "ready for development" | "next" => Status::READY,
+        "open" | "opened" | "new" => Status::TO_DO,
+        "doing" | "in development" | "started" => Status::IN_PROGRESS,
+        "in review" | "code review" | "in qa" | "testing" => Status::REVIEW,
+        "closed" | "resolved" | "fixed" => Status::COMPLETE,
+        "won't do" | "wont do" | "not planned" | "cancelled" | "canceled" => Status::ARCHIVE,
+        _ => return None,
+    };
+    Some(status)
+}
+
+// First label naming a status, scoped labels eg: 'workflow::doing' are read by
+// the part after '::'
+fn status_label<'a>(labels: impl IntoIterator<Item = &'a str>) -> Option<Status> {
+    labels
+        .into_iter()
+        .find_map(|l| status_of(l.rsplit("::").next().unwrap_or(l)))
+}
+
+fn is_epic_label(label: &str) -> bool {
+    label.trim().eq_ignore_ascii_case(EPIC_LABEL)
+}
+
+// Epics named as parents that aren't items themselves, eg: milestones, go first
+fn with_epics(items: Vec<Item>, epics: Vec<Item>) -> Vec<Row> {
+    let mut rows: Vec<Item> = Vec::new();
+    for epic in epics {
+        let exists = items
+            .iter()
+            .chain(&rows)
+            .any(|i| i.epic && i.title.eq_ignore_ascii_case(&epic.title));
+        if !exists {
+            rows.push(epic);
+        }
+    }
+    rows.extend(items);
+    rows.into_iter().map(Item::row).collect()
+}
+
+fn invalid_export(source: &str, err: impl std::fmt::Display) -> CliError {
+    CliError::input(format!("Unable to read the {source} export: {err}"))
+}
+
+fn string_at(value: &Value, key: &str) -> String {
+    match &value[key] {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn label_names(value: &Value) -> Vec<String> {
+    value["labels"]
+        .as_array()
+        .map(|labels| {
+            labels
+                .iter()
+                .map(|l| string_at(l, "name"))
+                .filter(|n| !n.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Issues of the REST API or the gh cli, pull requests are skipped. Milestones
+// become the epics of their issues
+fn github(text: &str) -> Result<Vec<Row>> {
+    let value: Value = serde_json::from_str(text).map_err(|err| invalid_export("GitHub", err))?;
+    let Some(issues) = value.as_array() else {
+        return Err(invalid_export("GitHub", "expected a list of issues"));
+    };
+    let mut items = Vec::new();
+    let mut epics = Vec::new();
+    for issue in issues.iter().filter(|i| i.get("pull_request").is_none()) {
+        let labels = label_names(issue);
+        let state = string_at(issue, "state").to_lowercase();
+        let reason = string_at(issue, "state_reason") + &string_at(issue, "stateReason");
+        let status = if reason.eq_ignore_ascii_case("not_planned") {
+            Status::ARCHIVE
+        } else if state == "closed" {
+            Status::COMPLETE
+        } else {
+            status_label(labels.iter().map(String::as_str)).unwrap_or(Status::TO_DO)
+        };
+        let milestone = &issue["milestone"];
+        let parent = match string_at(milestone, "title") {
+            t if t.is_empty() => None,
+            t => {
+                let due = string_at(milestone, "due_on") + &string_at(milestone, "dueOn");
+                epics.push(Item {
+                    id: format!("milestone:{t}"),
+                    title: t.clone(),
+                    description: string_at(milestone, "description"),
+                    status: if string_at(milestone, "state").eq_ignore_ascii_case("closed") {
+                        Status::COMPLETE
+                    } else {
+                        Status::TO_DO
+                    },
+                    epic: true,
+                    due_date: (!due.is_empty()).then_some(due),
+                    parent: None,
+                });
+                Some(t)
+            }
+        };
+        items.push(Item {
+            id: string_at(issue, "number"),
+            title: string_at(issue, "title"),
+            description: string_at(issue, "body"),
+            status,
+            epic: labels.iter().any(|l| is_epic_label(l)),
+            due_date: None,
+            parent,
+        });
+    }
+    Ok(with_epics(items, epics))
+}
+
+fn column<'a>(row: &'a Row, name: &str) -> &'a str {
+    row.iter()
+        .find(|(c, v)| c.eq_ignore_ascii_case(name) && !v.trim().is_empty())
+        .map_or("", |(_, v)| v.trim())
+}
+
+// Every value of a column, Jira repeats a column once per value eg: Labels
+fn columns<'a>(row: &'a Row, name: &str) -> Vec<&'a str> {
+    row.iter()
+        .filter(|(c, v)| c.eq_ignore_ascii_case(name) && !v.trim().is_empty())
+        .map(|(_, v)| v.trim())
+        .collect()
+}
+
+// GitLab issue csv, the epic of an issue is its parent
+fn gitlab(text: &str) -> Result<Vec<Row>> {
+    let mut items = Vec::new();
+    let mut epics = Vec::new();
+    for row in csv_rows(text)? {
+        let labels: Vec<&str> = column(&row, "Labels").split(',').map(str::trim).collect();
+        let status = if column(&row, "State").eq_ignore_ascii_case("closed") {
+            Status::COMPLETE
+        } else {
+            status_label(labels.iter().copied()).unwrap_or(Status::TO_DO)
+        };
+        let epic_title = column(&row, "Epic Title");
+        if !epic_title.is_empty() {
+            epics.push(Item {
+                id: format!("epic:{}", column(&row, "Epic ID")),
+                title: epic_title.to_string(),
+                description: String::new(),
+                status: Status::TO_DO,
+                epic: true,
+                due_date: None,
+                parent: None,
+            });
+        }
+        let due = column(&row, "Due Date");
+        items.push(Item {
+            id: column(&row, "Issue ID").to_string(),
+            title: column(&row, "Title").to_string(),
+            description: column(&row, "Description").to_string(),
+            status,
+            epic: labels.iter().any(|l| is_epic_label(l)),
+            due_date: (!due.is_empty()).then(|| due.to_string()),
+            parent: (!epic_title.is_empty()).then(|| epic_title.to_string()),
+        });
+    }
+    Ok(with_epics(items, epics))
+}
+
+// Jira writes dates like 01/Mar/26 12:00 AM
+fn jira_date(value: &str) -> String {
+    NaiveDateTime::parse_from_str(value, "%d/%b/%y %I:%M %p")
+        .map(|d| d.date())
+        .or_else(|_| NaiveDate::parse_from_str(value, "%d/%b/%y"))
+        .map_or(value.to_string(), |d| d.format("%Y-%m-%d").to_string())
+}
+
+fn jira_link(row: &Row) -> Option<&str> {
+    [
+        column(row, "Custom field (Epic Link)"),
+        column(row, "Parent"),
+        column(row, "Parent id"),
+    ]
+    .into_iter()
+    .find(|l| !l.is_empty())
+}
+
+// Follow the links until an epic or an issue without a parent, which becomes
+// an epic itself
+fn jira_epic<'a>(issues: &HashMap<&str, (bool, Option<&'a str>)>, mut key: &'a str) -> String {
+    for _ in 0..issues.len() {
+        match issues.get(key) {
+            Some((false, Some(link))) => key = link,
+            _ => break,
+        }
+    }
+    key.to_string()
+}
+
+// Jira issue csv, issues of type Epic are epics and the Epic Link or parent of
+// the others is their parent. Sub-tasks of a story go to the story's epic as
+// there is only one level of subtasks
+fn jira(text: &str) -> Result<Vec<Row>> {
+    let rows = csv_rows(text)?;
+    // Links refer to an issue by key or id, both are turned into the key
+    let mut keys: HashMap<&str, &str> = HashMap::new();
+    for row in &rows {
+        let key = column(row, "Issue key");
+        keys.insert(key, key);
+        keys.insert(column(row, "Issue id"), key);
+    }
+    // Key of each issue to its epic flag and the key it links to
+    let issues: HashMap<&str, (bool, Option<&str>)> = rows
+        .iter()
+        .map(|row| {
+            let epic = column(row, "Issue Type").eq_ignore_ascii_case(EPIC_LABEL);
+            let link = jira_link(row).and_then(|l| keys.get(l).copied());
+            (column(row, "Issue key"), (epic, link))
+        })
+        .collect();
+    let mut items = Vec::new();
+    for row in &rows {
+        let epic = column(row, "Issue Type").eq_ignore_ascii_case(EPIC_LABEL);
+        // Issues outside the export are matched by title
+        let parent = match (jira_link(row), column(row, "Parent summary")) {
+            (Some(l), summary) => match keys.get(l) {
+                Some(key) => Some(jira_epic(&issues, key)),
+                None if !summary.is_empty() => Some(summary.to_string()),
+                None => Some(l.to_string()),
+            },
+            (None, "") => None,
+            (None, summary) => Some(summary.to_string()),
+        };
+        let labels = columns(row, "Labels");
+        let status = status_of(column(row, "Status"))
+            .or_else(|| status_of(column(row, "Status Category")))
+            .or_else(|| status_label(labels))
+            .unwrap_or(Status::TO_DO);
+        let due = column(row, "Due Date");
+        items.push(Item {
+            id: column(row, "Issue key").to_string(),
+            title: column(row, "Summary").to_string(),
+            description: column(row, "Description").to_string(),
+            status,
+            epic,
+            due_date: (!due.is_empty()).then(|| jira_date(due)),
+            parent: if epic { None } else { parent },
+        });
+    }
+    Ok(with_epics(items, Vec::new()))
+}
+
+// Trello board json, the list of a card is its status. Cards labelled epic are
+// epics, other labels become the epic of their cards
+fn trello(text: &str) -> Result<Vec<Row>> {
+    let board: Value = serde_json::from_str(text).map_err(|err| invalid_export("Trello", err))?;
+    let Some(cards) = board["cards"].as_array() else {
+        return Err(invalid_export("Trello", "expected a board with cards"));
+    };
+    let lists: HashMap<String, &Value> = board["lists"]
+        .as_array()
+        .map(|lists| lists.iter().map(|l| (string_at(l, "id"), l)).collect())
+        .unwrap_or_default();
+    let mut items = Vec::new();
+    let mut epics = Vec::new();
+    for card in cards {
+        let list = lists.get(&string_at(card, "idList"));
+        let closed = card["closed"].as_bool().unwrap_or_default()
+            || list.is_some_and(|l| l["closed"].as_bool().unwrap_or_default());
+        let status = if closed {
+            Status::ARCHIVE
+        } else {
+            list.and_then(|l| status_of(&string_at(l, "name")))
+                .unwrap_or(Status::TO_DO)
+        };
+        let labels = label_names(card);
+        let epic = labels.iter().any(|l| is_epic_label(l));
+        let parent = labels.iter().find(|l| !is_epic_label(l)).cloned();
+        if let (Some(p), false) = (&parent, epic) {
+            epics.push(Item {
+                id: format!("label:{p}"),
+                title: p.clone(),
+                description: String::new(),
+                status: Status::TO_DO,
+                epic: true,
+                due_date: None,
+                parent: None,
+            });
+        }
+        let due = string_at(card, "due");
+        items.push(Item {
+            id: string_at(card, "id"),
+            title: string_at(card, "name"),
+            description: string_at(card, "desc"),
+            status,
+            epic,
+            due_date: (!due.is_empty()).then_some(due),
+            parent: if epic { None } else { parent },
+        });
+    }
+    Ok(with_epics(items, epics))
+}
+
+// Rows of the import file in the fields read by import
+pub fn rows(source: Source, text: &str) -> Result<Vec<Row>> {
+    match source {
+        Source::Github => github(text),
+        Source::Gitlab => gitlab(text),
+        Source::Jira => jira(text),
+        Source::Trello => trello(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get<'a>(row: &'a Row, field: &str) -> &'a str {
+        column(row, field)
+    }
+
+    #[test]
+    fn github_milestones_and_trello_labels_become_epics() {
+        let issues = r#"[
+            {"number": 1, "title": "Checkout", "body": "", "state": "open", "labels": [{"name": "epic"}]},
+            {"number": 2, "title": "Cart", "body": "b", "state": "open", "labels": [{"name": "in progress"}],
+             "milestone": {"title": "Checkout", "due_on": "2026-03-01T07:00:00Z"}},
+            {"number": 3, "title": "Search", "state": "closed", "labels": [],
+             "milestone": {"title": "v2", "due_on": null}},
+            {"number": 4, "title": "PR", "state": "open", "pull_request": {}}
+        ]"#;
+        let rows = github(issues).unwrap();
+        let titles: Vec<&str> = rows.iter().map(|r| get(r, "title")).collect();
+        assert_eq!(titles, vec!["v2", "Checkout", "Cart", "Search"]);
+        assert_eq!(get(&rows[2], "status"), "IN_PROGRESS");
+        assert_eq!(get(&rows[2], "parent"), "Checkout");
+        assert_eq!(get(&rows[3], "status"), "COMPLETE");
+        assert_eq!(get(&rows[3], "issue"), "2");
+
+        let board = r#"{
+            "lists": [{"id": "l1", "name": "Doing", "closed": false}, {"id": "l2", "name": "Done", "closed": false}],
+            "cards": [
+                {"id": "c1", "name": "Hero", "desc": "", "idList": "l1", "closed": false,
+                 "due": "2026-04-01T12:00:00.000Z", "labels": [{"name": "Landing"}]},
+                {"id": "c2", "name": "Footer", "desc": "", "idList": "l2", "closed": true, "due": null, "labels": []}
+            ]
+        }"#;
+        let rows = trello(board).unwrap();
+        let titles: Vec<&str> = rows.iter().map(|r| get(r, "title")).collect();
+        assert_eq!(titles, vec!["Landing", "Hero", "Footer"]);
+        assert_eq!(get(&rows[1], "status"), "IN_PROGRESS");
+        assert_eq!(get(&rows[1], "parent"), "Landing");
+        assert_eq!(get(&rows[2], "status"), "ARCHIVE");
+    }
+
+    #[test]
+    fn jira_and_gitlab_csv() {
+        let jira_csv = "Summary,Issue key,Issue id,Issue Type,Status,Due Date,Labels,Labels,Custom field (Epic Link),Parent id\n\
+            Checkout,SHOP-1,100,Epic,To Do,,,,,\n\
+            Cart,SHOP-2,101,Story,In Review,01/Mar/26 12:00 AM,ui,,SHOP-1,\n\
+            Search,SHOP-3,102,Task,Backlog,,,,,\n\
+            Cart button,SHOP-4,103,Sub-task,To Do,,,,,101\n\
+            Search box,SHOP-5,104,Sub-task,To Do,,,,,102\n";
+        let rows = jira(jira_csv).unwrap();
+        assert_eq!(get(&rows[0], "issue"), "1");
+        assert_eq!(get(&rows[1], "parent"), "SHOP-1");
+        // Sub-tasks of a story go to its epic, a story without one is an epic
+        assert_eq!(get(&rows[3], "parent"), "SHOP-1");
+        assert_eq!(get(&rows[4], "parent"), "SHOP-3");
+        assert_eq!(get(&rows[1], "status"), "REVIEW");
+        assert_eq!(get(&rows[1], "due_date"), "2026-03-01");
+        assert_eq!(get(&rows[2], "status"), "DRAFT");
+
+        let gitlab_csv = "Issue ID,Title,Description,State,Due Date,Labels,Epic ID,Epic Title\n\
+            7,Cart,,Open,2026-03-01,\"frontend, workflow::doing\",3,Checkout\n\
+            8,Search,,Closed,,,,\n";
+        let rows = gitlab(gitlab_csv).unwrap();
+        let titles: Vec<&str> = rows.iter().map(|r| get(r, "title")).collect();
+        assert_eq!(titles, vec!["Checkout", "Cart", "Search"]);
+        assert_eq!(get(&rows[1], "status"), "IN_PROGRESS");
+        assert_eq!(get(&rows[1], "parent"), "Checkout");
+        assert_eq!(get(&rows[2], "status"), "COMPLETE");
+    }
+}
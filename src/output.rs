@@ -0,0 +1,171 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{self, Write};
+
+use crate::cli_error::{self, CliError};
+
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Jsonl,
+    Yaml,
+    Csv,
+}
+
+impl OutputFormat {
+    pub fn is_table(&self) -> bool {
+        *self == OutputFormat::Table
+    }
+}
+
+pub fn print_list<T: Serialize>(items: &[T], format: OutputFormat) -> cli_error::Result<()> {
+    write_list(&mut io::stdout(), items, format).map_err(output_error)
+}
+
+pub fn print_item<T: Serialize>(item: &T, format: OutputFormat) -> cli_error::Result<()> {
+    write_item(&mut io::stdout(), item, format).map_err(output_error)
+}
+
+fn output_error(err: anyhow::Error) -> CliError {
+    CliError::Io(io::Error::other(format!("Unable to write output: {err}")))
+}
+
+pub fn write_list<T: Serialize>(
+    out: &mut impl Write,
+    items: &[T],
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            serde_json::to_writer_pretty(&mut *out, items)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Jsonl => {
+            for item in items {
+                serde_json::to_writer(&mut *out, item)?;
+                writeln!(out)?;
+            }
+        }
+        OutputFormat::Yaml => serde_yaml::to_writer(&mut *out, items)?,
+        OutputFormat::Csv => {
+            let values = items
+                .iter()
+                .map(serde_json::to_value)
+                .collect::<serde_json::Result<Vec<Value>>>()?;
+            write_csv(out, &values)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn write_item<T: Serialize>(
+    out: &mut impl Write,
+    item: &T,
+    format: OutputFormat,
+) -> Result<()> {
+    match format {
+        OutputFormat::Json | OutputFormat::Table => {
+            serde_json::to_writer_pretty(&mut *out, item)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Jsonl => {
+            serde_json::to_writer(&mut *out, item)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Yaml => serde_yaml::to_writer(&mut *out, item)?,
+        OutputFormat::Csv => write_csv(out, &[serde_json::to_value(item)?])?,
+    }
+    Ok(())
+}
+
+// Nested objects become dotted columns eg: created_by.email,
+// arrays are kept as a json string in a single column
+pub fn flatten(prefix: &str, value: &Value, row: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, v) in map {
+                let column = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                flatten(&column, v, row);
+            }
+        }
+        Value::Null => row.push((prefix.to_string(), String::new())),
+        Value::String(s) => row.push((prefix.to_string(), s.clone())),
+        Value::Array(_) => row.push((prefix.to_string(), value.to_string())),
+        other => row.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+fn write_csv(out: &mut impl Write, values: &[Value]) -> Result<()> {
+    let rows: Vec<Vec<(String, String)>> = values
+        .iter()
+        .map(|v| {
+            let mut row = Vec::new();
+            flatten("", v, &mut row);
+            row
+        })
+        .collect();
+
+    // Optional fields can be missing from some rows, collect every column
+    let mut headers: Vec<String> = Vec::new();
+    for row in &rows {
+        for (column, _) in row {
+            if !headers.contains(column) {
+                headers.push(column.clone());
+            }
+        }
+    }
+
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(&headers)?;
+    for row in &rows {
+        let record = headers.iter().map(|h| {
+            row.iter()
+                .find(|(column, _)| column == h)
+                .map(|(_, v)| v.as_str())
+                .unwrap_or_default()
+        });
+        writer.write_record(record)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn csv_flattens_nested_objects() {
+        let values = vec![
+            json!({"code": "WEB-1", "created_by": {"id": 1, "email": "a@b.c"}, "subtasks": []}),
+            json!({"code": "WEB-2", "created_by": {"id": 2, "email": "d@e.f"}, "due_date": null}),
+        ];
+        let mut out = Vec::new();
+        write_csv(&mut out, &values).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            lines[0],
+            "code,created_by.id,created_by.email,subtasks,due_date"
+        );
+        assert_eq!(lines[1], "WEB-1,1,a@b.c,[],");
+        assert_eq!(lines[2], "WEB-2,2,d@e.f,,");
+    }
+
+    #[test]
+    fn jsonl_writes_one_line_per_item() {
+        let items = vec![json!({"id": 1}), json!({"id": 2})];
+        let mut out = Vec::new();
+        write_list(&mut out, &items, OutputFormat::Jsonl).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "{\"id\":1}\n{\"id\":2}\n");
+    }
+}
@@ -0,0 +1,78 @@
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+// Which part of a list to fetch when the API paginates it
+#[derive(Debug, Default, Clone)]
+pub struct Pages {
+    // Fetch only this page, next links are not followed
+    pub page: Option<u32>,
+    // Stop once this many items were fetched
+    pub limit: Option<usize>,
+    // Most pages to follow, every next link is followed when None
+    pub max_pages: Option<usize>,
+}
+
+// Items fetched from a list endpoint
+#[derive(Debug)]
+pub struct Listing<T> {
+    pub items: Vec<T>,
+    // Total reported by the API, only known for paginated responses
+    pub count: Option<u64>,
+    // Link of the first page not fetched because of max_pages
+    pub next: Option<String>,
+    // Items were dropped client side, count no longer adds up
+    pub filtered: bool,
+}
+
+// DRF pagination body
+#[derive(Deserialize)]
+struct Page<T> {
+    count: Option<u64>,
+    next: Option<String>,
+    results: Vec<T>,
+}
+
+// A list endpoint returns either a plain array or a DRF page,
+// a plain array is read as a single page without a next link
+pub(crate) fn parse_page<T: DeserializeOwned>(
+    value: Value,
+) -> serde_json::Result<(Vec<T>, Option<u64>, Option<String>)> {
+    if value.is_object() {
+        let page: Page<T> = serde_json::from_value(value)?;
+        Ok((page.results, page.count, page.next))
+    } else {
+        Ok((serde_json::from_value(value)?, None, None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn plain_list_is_one_page() {
+        let (items, count, next) = parse_page::<i32>(json!([1, 2, 3])).unwrap();
+        assert_eq!(items, vec![1, 2, 3]);
+        assert_eq!(count, None);
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn drf_page_is_read() {
+        let value = json!({
+            "count": 5,
+            "next": "http://localhost/api/v1/tasks?page=2",
+            "previous": null,
+            "results": [1, 2]
+        });
+        let (items, count, next) = parse_page::<i32>(value).unwrap();
+        assert_eq!(items, vec![1, 2]);
+        assert_eq!(count, Some(5));
+        assert_eq!(
+            next.as_deref(),
+            Some("http://localhost/api/v1/tasks?page=2")
+        );
+    }
+}
@@ -1,27 +1,82 @@
-use crate::description::text_editor;
-use crate::response::{ClientErrorResponse, ProjectResponse};
+use crate::cache::{cache, offline_project, offline_projects};
+use crate::cli_error::{CliError, Result};
+use crate::config::read_config_file;
+use crate::description::{read_description_file, text_editor};
+use crate::output::{print_item, print_list};
+use crate::prompt::{input, is_interactive, require_interactive, required_input};
 use crate::service::{
-    delete_confirmation, get_request, RequestType, CLIENT_ERROR, CLIENT_RESPONSE_ERROR,
+    delete_confirmation, get_client, send_with_retry, warn_truncated, PageArgs, RequestType,
 };
+use crate::table::{print_table, TableArgs};
+use crate::GlobalArgs;
 
 use clap::{Parser, Subcommand};
-use cli_table::{print_stdout, WithTitle};
 use colored::Colorize;
-use std::collections::HashMap;
-use std::io::{self, Write};
+use umsebenzi::request::ProjectRequest;
+use umsebenzi::response::ProjectResponse;
 
-const PROJECT_ENDPOINT: &str = "/projects";
 const PROJECT_TITLE_ERROR: &str = "Project title expected";
 const PROJECT_CODE_ERROR: &str = "Project code expected";
 const PROJECT_DESCRIPTION_ERROR: &str = "Project description expected";
 
+#[derive(Parser, Debug)]
+pub struct ProjectFormArgs {
+    #[arg(long)]
+    title: Option<String>,
+    #[arg(long)]
+    code: Option<String>,
+    #[arg(long, conflicts_with = "description_file")]
+    description: Option<String>,
+    /// Read the description from a file, use '-' for stdin
+    #[arg(long, value_name = "FILE")]
+    description_file: Option<String>,
+}
+
+impl ProjectFormArgs {
+    fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.code.is_none()
+            && self.description.is_none()
+            && self.description_file.is_none()
+    }
+
+    // Description given through either --description or --description-file
+    fn description(&self) -> Result<Option<String>> {
+        if let Some(d) = &self.description {
+            return Ok(Some(d.clone()));
+        }
+        let Some(path) = self.description_file.as_ref() else {
+            return Ok(None);
+        };
+        match read_description_file(path) {
+            Ok(d) => Ok(Some(d)),
+            Err(err) => Err(CliError::input(format!(
+                "{PROJECT_DESCRIPTION_ERROR}: {err}"
+            ))),
+        }
+    }
+}
+
 #[derive(Subcommand, Debug)]
 enum ProjectCLI {
-    List,
-    Add,
-    Detail { project_id: String },
-    Edit { project_id: String },
-    Delete { project_id: String },
+    List {
+        #[command(flatten)]
+        pages: PageArgs,
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    Add(ProjectFormArgs),
+    Detail {
+        project_id: String,
+    },
+    Edit {
+        project_id: String,
+        #[command(flatten)]
+        form: ProjectFormArgs,
+    },
+    Delete {
+        project_id: String,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -30,287 +85,224 @@ pub struct ProjectArgs {
     command: ProjectCLI,
 }
 
-pub fn run(args: ProjectArgs) {
+pub fn run(args: ProjectArgs, global: &GlobalArgs) -> Result<()> {
     match args.command {
-        ProjectCLI::List => list(),
-        ProjectCLI::Add => add(),
-        ProjectCLI::Delete { project_id } => delete(project_id),
-        ProjectCLI::Detail { project_id } => detail(project_id),
-        ProjectCLI::Edit { project_id } => edit(project_id),
+        ProjectCLI::List { pages, table } => list(pages, table, global),
+        ProjectCLI::Add(a) => add(a, global),
+        ProjectCLI::Delete { project_id } => delete(project_id, global),
+        ProjectCLI::Detail { project_id } => detail(project_id, global),
+        ProjectCLI::Edit { project_id, form } => edit(project_id, form, global),
     }
 }
 
-fn list() {
-    let request = match get_request(PROJECT_ENDPOINT, None) {
-        Ok(r) => r,
-        Err(err) => {
-            eprintln!("{}: {err}", CLIENT_ERROR.red().bold());
-            std::process::exit(1);
+fn list(pages: PageArgs, table: TableArgs, global: &GlobalArgs) -> Result<()> {
+    table.check::<ProjectResponse>()?;
+    let mut projects = if global.offline {
+        let mut projects = offline_projects(global)?;
+        if let Some(limit) = pages.pages().limit {
+            projects.truncate(limit);
         }
-    };
-
-    let resp = match request.client.get(request.url).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprintln!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
+        projects
+    } else {
+        let client = get_client(global)?;
+        let listing = client.list_projects_pages(&pages.pages())?;
+        warn_truncated(&listing, "projects");
+        if let Some(c) = cache(global) {
+            c.save_projects(
+                &listing.items,
+                !pages.is_partial() && listing.next.is_none(),
+            );
         }
+        listing.items
     };
-    if resp.status().is_success() {
-        let proj: Vec<ProjectResponse> = match resp.json() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err:?}", "Unable to parse response json".red());
-                std::process::exit(1);
-            }
-        };
-        let _ = print_stdout(proj.with_title()).is_ok();
+    table.sort(&mut projects)?;
+    if global.output.is_table() {
+        let configured = read_config_file().ok().and_then(|f| f.columns.project);
+        print_table(&projects, &table.columns::<ProjectResponse>(configured)?)
     } else {
-        println!("{} {}", "Error".red().bold(), resp.status());
+        print_list(&projects, global.output)
     }
-    //
 }
 
-fn add() {
-    println!("{}", "Create a new project".green().bold());
-
-    print!("{}: ", "Title".green().bold());
-    let _ = io::stdout().flush();
-    let mut title_buf = String::new();
-    io::stdin()
-        .read_line(&mut title_buf)
-        .expect(&PROJECT_TITLE_ERROR.red().bold());
-    if title_buf.trim().is_empty() {
-        eprintln!("{}", PROJECT_TITLE_ERROR.red().bold());
-        std::process::exit(1);
-    }
-    print!("{}: ", "Code".green().bold());
-    let _ = io::stdout().flush();
-    let mut code_buf = String::new();
-    io::stdin()
-        .read_line(&mut code_buf)
-        .expect(&PROJECT_CODE_ERROR.red());
-    if code_buf.trim().is_empty() {
-        eprintln!("{}", PROJECT_CODE_ERROR.red().bold());
-        std::process::exit(1);
+fn add(args: ProjectFormArgs, global: &GlobalArgs) -> Result<()> {
+    if args.is_empty() && is_interactive() {
+        println!("{}", "Create a new project".green().bold());
     }
-    print!(
-        "{}: ",
-        "Description [type 'Y' to open editor]".green().bold()
-    );
-    let _ = io::stdout().flush();
-    let mut answer_buf = String::new();
-    io::stdin()
-        .read_line(&mut answer_buf)
-        .expect(&PROJECT_DESCRIPTION_ERROR.red());
-    let mut description = String::new();
-    if answer_buf.trim() == "Y" {
-        description = text_editor(None).expect(&PROJECT_DESCRIPTION_ERROR.red().bold());
-    } else {
-        eprintln!("{}", "Invalid Command expected Y".red().bold());
-        std::process::exit(1);
-    }
-
-    let mut project_body = HashMap::new();
-    project_body.insert("title", title_buf);
-    project_body.insert("description", description);
-    project_body.insert("code", code_buf);
 
-    let request = match get_request(PROJECT_ENDPOINT, None) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
+    let title = match &args.title {
+        Some(t) => t.clone(),
+        None => {
+            require_interactive(PROJECT_TITLE_ERROR, "--title")?;
+            required_input("Title", PROJECT_TITLE_ERROR)?
         }
     };
-    let resp = match request.client.post(request.url).json(&project_body).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
+    let code = match &args.code {
+        Some(c) => c.clone(),
+        None => {
+            require_interactive(PROJECT_CODE_ERROR, "--code")?;
+            required_input("Code", PROJECT_CODE_ERROR)?
         }
     };
-    if resp.status().is_success() {
-        println!("{}", "Project created".green().bold())
-    } else if resp.status().is_client_error() {
-        let response: ClientErrorResponse = resp.json().unwrap();
-        println!("{}: {:?}", "error".red(), response);
-    } else {
-        println!("{}: {}", "Error".red().bold(), resp.status());
-        println!("{}: {}", "Error".red().bold(), resp.text().unwrap());
-    };
-}
-
-fn edit(project_id: String) {
-    // First get the exising project
-    let proj: ProjectResponse;
-    let request = match get_request(PROJECT_ENDPOINT, Some(&project_id)) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
+    let description = match args.description()? {
+        Some(d) => d,
+        None => {
+            require_interactive(
+                PROJECT_DESCRIPTION_ERROR,
+                "--description or --description-file",
+            )?;
+            if input("Description [type 'Y' to open editor]")? == "Y" {
+                text_editor(None)?
+            } else {
+                return Err(CliError::input("Invalid Command expected Y"));
+            }
         }
     };
-    let resp = match request.client.get(request.url).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
-        }
+
+    let mut project = ProjectRequest {
+        title,
+        description,
+        code,
     };
-    // Update existing project
-    if resp.status().is_success() {
-        proj = match resp.json() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{} {err}", "Unable to parse response json".red());
-                std::process::exit(1);
+
+    let client = get_client(global)?;
+    let created = send_with_retry(&mut project, |p| client.create_project(p), reprompt_field)?;
+    if let Some(c) = cache(global) {
+        match created["id"].as_i64() {
+            Some(id) => c.save_written_project(&client, &id.to_string(), Some(created.clone())),
+            // Without the ID in the response every project is fetched again
+            None => {
+                if let Ok(projects) = client.list_projects() {
+                    c.save_projects(&projects, true);
+                }
             }
-        };
-        // Update values
-        print!("{}: ", "Title [leave blank to use existing]".green().bold());
-        let _ = io::stdout().flush();
-        let mut title_buf = String::new();
-        io::stdin()
-            .read_line(&mut title_buf)
-            .expect(&PROJECT_TITLE_ERROR.red());
-        if title_buf.trim().is_empty() {
-            title_buf = proj.title
         }
+    }
+    if global.output.is_table() {
+        println!("{}", "Project created".green().bold());
+        Ok(())
+    } else {
+        print_item(&created, global.output)
+    }
+}
 
-        print!("{}: ", "Code: [leave blank to use existing]".green().bold());
-        let _ = io::stdout().flush();
-        let mut code_buf = String::new();
-        io::stdin()
-            .read_line(&mut code_buf)
-            .expect(&PROJECT_CODE_ERROR.red());
-        if code_buf.trim().is_empty() {
-            code_buf = proj.code
-        }
+fn edit(project_id: String, args: ProjectFormArgs, global: &GlobalArgs) -> Result<()> {
+    // First get the exising project
+    let client = get_client(global)?;
+    let proj = client.get_project(&project_id)?;
 
-        print!(
-            "{}: ",
-            "Description: [Type E to edit. leave blank to use existing]"
-                .green()
-                .bold()
-        );
-        let _ = io::stdout().flush();
-        let mut description_buf = String::new();
-        io::stdin()
-            .read_line(&mut description_buf)
-            .expect(&PROJECT_DESCRIPTION_ERROR.red());
-        if description_buf.trim().is_empty() {
-            description_buf = proj.description
-        } else if description_buf.trim() == "E" {
-            description_buf =
-                text_editor(Some(proj.description)).expect(&PROJECT_DESCRIPTION_ERROR.red());
+    // Flags replace the prompts, values not given keep the existing ones
+    let mut project = if args.is_empty() {
+        prompt_edit(proj)?
+    } else {
+        let description = args.description()?.unwrap_or(proj.description);
+        ProjectRequest {
+            title: args.title.unwrap_or(proj.title),
+            code: args.code.unwrap_or(proj.code),
+            description,
         }
+    };
 
-        let mut project_body = HashMap::new();
-        project_body.insert("title", title_buf);
-        project_body.insert("description", description_buf);
-        project_body.insert("code", code_buf);
-
-        let request = match get_request(PROJECT_ENDPOINT, Some(&project_id)) {
-            Ok(c) => c,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        let resp = match request.client.put(request.url).json(&project_body).send() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        if resp.status().is_success() {
-            println!("{}", "Project Updated".green().bold())
-        } else if resp.status().is_client_error() {
-            let response: ClientErrorResponse = resp.json().unwrap();
-            println!("{}: {:?}", "error".red(), response);
-        } else {
-            println!("{}: {}", "Error".red().bold(), resp.status());
-            println!("{}: {}", "Error".red().bold(), resp.text().unwrap());
-        };
-    } else if resp.status().is_client_error() {
-        eprintln!("{}: Unable to find project code", "Error".red());
+    let updated = send_with_retry(
+        &mut project,
+        |p| client.update_project(&project_id, p),
+        reprompt_field,
+    )?;
+    if let Some(c) = cache(global) {
+        c.save_written_project(&client, &project_id, Some(updated.clone()));
+    }
+    if global.output.is_table() {
+        println!("{}", "Project Updated".green().bold());
+        Ok(())
     } else {
-        eprintln!(
-            "{}: Unable to fetch project details:  {:?}",
-            "Error".red().bold(),
-            resp.status()
-        );
-        eprintln!("{}: {:?}", "Error".red().bold(), resp.text().ok());
+        print_item(&updated, global.output)
     }
 }
 
-fn delete(project_id: String) {
-    let is_delete = delete_confirmation(&project_id, RequestType::PROJECT);
+// Ask again for a field rejected by the API
+fn reprompt_field(project: &mut ProjectRequest, field: &str) -> Result<()> {
+    match field {
+        "title" => project.title = required_input("Title", PROJECT_TITLE_ERROR)?,
+        "code" => project.code = required_input("Code", PROJECT_CODE_ERROR)?,
+        "description" => project.description = text_editor(Some(project.description.clone()))?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn prompt_edit(proj: ProjectResponse) -> Result<ProjectRequest> {
+    let mut title = input("Title [leave blank to use existing]")?;
+    if title.is_empty() {
+        title = proj.title
+    }
+
+    let mut code = input("Code: [leave blank to use existing]")?;
+    if code.is_empty() {
+        code = proj.code
+    }
+
+    let mut description = input("Description: [Type E to edit. leave blank to use existing]")?;
+    if description.is_empty() {
+        description = proj.description
+    } else if description == "E" {
+        description = text_editor(Some(proj.description))?;
+    }
+    Ok(ProjectRequest {
+        title,
+        description,
+        code,
+    })
+}
+
+fn delete(project_id: String, global: &GlobalArgs) -> Result<()> {
+    let is_delete = delete_confirmation(&project_id, RequestType::PROJECT)?;
     if is_delete {
-        let request = match get_request(PROJECT_ENDPOINT, Some(&project_id)) {
-            Ok(c) => c,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        let resp = match request.client.delete(request.url).send() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        if resp.status().is_success() {
+        let client = get_client(global)?;
+        client.delete_project(&project_id)?;
+        if let Some(c) = cache(global) {
+            c.remove_project(&project_id);
+        }
+        if global.output.is_table() {
             println!("{}", "Project Deleted".green().bold());
-        } else if resp.status().is_client_error() {
-            eprintln!("{}: Unable to delete Project:  {:?}", "Error".red(), resp);
-            std::process::exit(1);
         }
     }
+    Ok(())
 }
 
-fn detail(project_id: String) {
-    let request = match get_request(PROJECT_ENDPOINT, Some(&project_id)) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
-        }
-    };
-    let resp = match request.client.get(request.url).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
+// Project by code, from every project or the cache with --offline
+pub fn find_project(code: &str, global: &GlobalArgs) -> Result<ProjectResponse> {
+    if global.offline {
+        return offline_project(global, code);
+    }
+    let projects = get_client(global)?.list_projects()?;
+    if let Some(c) = cache(global) {
+        c.save_projects(&projects, true);
+    }
+    projects
+        .into_iter()
+        .find(|p| p.code.eq_ignore_ascii_case(code))
+        .ok_or_else(|| CliError::input(format!("No project with code {code}")))
+}
+
+fn detail(project_id: String, global: &GlobalArgs) -> Result<()> {
+    let proj = if global.offline {
+        offline_project(global, &project_id)?
+    } else {
+        let proj = get_client(global)?.get_project(&project_id)?;
+        if let Some(c) = cache(global) {
+            c.save_projects(std::slice::from_ref(&proj), false);
         }
+        proj
     };
-    if resp.status().is_success() {
-        let proj: ProjectResponse = match resp.json() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err}", "Unable to parse response json".red().bold());
-                std::process::exit(1);
-            }
-        };
-        println!("ID: {}", proj.id);
-        println!("Title: {}", proj.title);
-        println!("Code: {}", proj.code);
-        println!("Created By: {}", proj.created_by);
-        println!("Created At: {}", proj.created_at);
-        println!("Modified At: {}", proj.modified_at);
-        println!("Description:");
-        println!("{}", proj.description)
-    } else if resp.status().is_client_error() {
-        eprintln!(
-            "{}: {} Unable to find project code",
-            "Error".red(),
-            resp.status()
-        );
-    } else {
-        eprintln!("Unable to fetch project details:  {:?}", resp.status());
-        eprintln!("{:?}", resp.text().ok());
+    if !global.output.is_table() {
+        return print_item(&proj, global.output);
     }
+    println!("ID: {}", proj.id);
+    println!("Title: {}", proj.title);
+    println!("Code: {}", proj.code);
+    println!("Created By: {}", proj.created_by);
+    println!("Created At: {}", proj.created_at);
+    println!("Modified At: {}", proj.modified_at);
+    println!("Description:");
+    println!("{}", proj.description);
+    Ok(())
 }
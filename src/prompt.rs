@@ -0,0 +1,34 @@
+use colored::Colorize;
+use std::io::{self, IsTerminal, Write};
+
+use crate::cli_error::{CliError, Result};
+
+// Prompts are only shown when a user is sitting at the terminal,
+// scripts piping into the cli get an error for missing values instead
+pub fn is_interactive() -> bool {
+    io::stdin().is_terminal()
+}
+
+pub fn input(label: &str) -> Result<String> {
+    print!("{}: ", label.green().bold());
+    io::stdout().flush()?;
+    let mut buf = String::new();
+    io::stdin().read_line(&mut buf)?;
+    Ok(buf.trim().to_string())
+}
+
+pub fn required_input(label: &str, error: &str) -> Result<String> {
+    let value = input(label)?;
+    if value.is_empty() {
+        return Err(CliError::input(error));
+    }
+    Ok(value)
+}
+
+// Fail with the field error when the value can't be asked for
+pub fn require_interactive(error: &str, flag: &str) -> Result<()> {
+    if !is_interactive() {
+        return Err(CliError::input(format!("{error}: use {flag}")));
+    }
+    Ok(())
+}
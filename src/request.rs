@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct TaskRequest {
     pub project_id: i32,
     pub title: String,
@@ -13,3 +13,10 @@ pub struct TaskRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub parent_id: Option<i32>,
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ProjectRequest {
+    pub title: String,
+    pub description: String,
+    pub code: String,
+}
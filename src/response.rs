@@ -5,7 +5,8 @@ use std::fmt;
 use crate::enums::Status;
 
 
-#[derive(Debug, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum UserID{
     IntId(i32),
@@ -26,11 +27,11 @@ impl fmt::Display for UserID {
     
 }
 
-#[derive(Serialize, Deserialize, Debug, Table)]
+#[derive(Serialize, Deserialize, Debug, Clone, Table)]
 pub struct User {
     pub id: UserID,
-    username: String,
-    email: String,
+    pub username: String,
+    pub email: String,
 }
 impl fmt::Display for User {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -38,7 +39,7 @@ impl fmt::Display for User {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Table)]
+#[derive(Serialize, Deserialize, Debug, Clone, Table)]
 pub struct ProjectResponse {
     pub id: i32,
     pub created_by: User,
@@ -57,12 +58,12 @@ impl fmt::Display for ProjectResponse {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Table)]
+#[derive(Serialize, Deserialize, Debug, Clone, Table)]
 pub struct ProjectTaskResponse {
     pub id: i32,
-    title: String,
-    code: String,
-    created_at: String,
+    pub title: String,
+    pub code: String,
+    pub created_at: String,
 }
 
 impl fmt::Display for ProjectTaskResponse {
@@ -71,45 +72,37 @@ impl fmt::Display for ProjectTaskResponse {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Table)]
+#[derive(Serialize, Deserialize, Debug, Clone, Table)]
 pub struct SubTaskResponse {
-    title: String,
-    code: String,
-    status: String,
-    created_at: String,
+    pub title: String,
+    pub code: String,
+    pub status: String,
+    pub created_at: String,
 }
 
 fn display_subtasks(tasks: &Option<Vec<SubTaskResponse>>) -> impl fmt::Display {
     if let Some(v) = tasks {
         if v.is_empty() {
-            format!("No")
+            "No".to_string()
         } else {
-            format!("Yes")
+            "Yes".to_string()
         }
     } else {
-        format!("----")
+        "----".to_string()
     }
 }
 
 fn display_due_date(tasks: &Option<String>) -> impl fmt::Display {
     if let Some(v) = tasks {
-        format!("{}", v)
+        v.to_string()
     } else {
-        format!("N/A")
+        "N/A".to_string()
     }
 }
 
-fn display_parent(tasks: &Option<i32>) -> impl fmt::Display {
-    if let Some(v) = tasks {
-        format!("{}", v)
-    } else {
-        format!("N/A")
-    }
-}
-
-#[derive(Serialize, Deserialize, Debug, Table)]
+#[derive(Serialize, Deserialize, Debug, Clone, Table)]
 pub struct TaskResponse {
-    id: i32,
+    pub id: i32,
     #[table(skip)]
     pub project: ProjectTaskResponse,
     pub title: String,
@@ -123,17 +116,29 @@ pub struct TaskResponse {
     #[table(display_fn = "display_due_date")]
     pub due_date: Option<String>,
     #[table(skip)]
-    modified_at: String,
+    pub modified_at: String,
     #[table(display_fn = "display_subtasks")]
     pub subtasks: Option<Vec<SubTaskResponse>>,
     #[table(skip)]
     pub assigned_to: User,
     pub created_at: String,
     #[table(skip)]
-    #[table(display_fn = "display_parent")]
     pub parent: Option<i32>,
 }
 
+// Keys of errors that don't belong to a single request field
+pub const NON_FIELD_KEYS: [&str; 2] = ["detail", "non_field_errors"];
+
+fn push_errors(
+    errors: &mut Vec<(&'static str, String)>,
+    field: &'static str,
+    messages: &Option<Vec<String>>,
+) {
+    if let Some(m) = messages {
+        errors.extend(m.iter().map(|message| (field, message.clone())));
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ClientErrorResponse {
     pub title: Option<Vec<String>>,
@@ -156,3 +161,56 @@ pub struct TaskErrorResponse {
     pub detail: Option<String>,
     pub non_field_errors: Option<Vec<String>>,
 }
+
+// Error body of endpoints without request fields, eg: /me
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DetailErrorResponse {
+    pub detail: Option<String>,
+    pub non_field_errors: Option<Vec<String>>,
+}
+
+impl ClientErrorResponse {
+    // One (field, message) pair per message, in request field order
+    pub fn field_errors(&self) -> Vec<(&'static str, String)> {
+        let mut errors = Vec::new();
+        push_errors(&mut errors, "title", &self.title);
+        push_errors(&mut errors, "code", &self.code);
+        push_errors(&mut errors, "description", &self.description);
+        push_errors(&mut errors, "non_field_errors", &self.non_field_errors);
+        if let Some(d) = &self.detail {
+            errors.push(("detail", d.clone()));
+        }
+        errors
+    }
+}
+
+impl TaskErrorResponse {
+    // One (field, message) pair per message, in request field order
+    pub fn field_errors(&self) -> Vec<(&'static str, String)> {
+        let mut errors = Vec::new();
+        push_errors(&mut errors, "project_id", &self.project_id);
+        push_errors(&mut errors, "title", &self.title);
+        push_errors(&mut errors, "description", &self.description);
+        push_errors(&mut errors, "issue", &self.issue);
+        push_errors(&mut errors, "parent_id", &self.parent_id);
+        push_errors(&mut errors, "status", &self.status);
+        push_errors(&mut errors, "due_date", &self.due_date);
+        push_errors(&mut errors, "assigned_to_id", &self.assigned_to_id);
+        push_errors(&mut errors, "non_field_errors", &self.non_field_errors);
+        if let Some(d) = &self.detail {
+            errors.push(("detail", d.clone()));
+        }
+        errors
+    }
+}
+
+impl DetailErrorResponse {
+    pub fn field_errors(&self) -> Vec<(&'static str, String)> {
+        let mut errors = Vec::new();
+        push_errors(&mut errors, "non_field_errors", &self.non_field_errors);
+        if let Some(d) = &self.detail {
+            errors.push(("detail", d.clone()));
+        }
+        errors
+    }
+}
@@ -1,17 +1,74 @@
-use crate::config::read_toml_file;
-use crate::config::Data;
-use anyhow::Result;
+use crate::cache::{cache, Cache};
+use crate::cli_error::{CliError, Result};
+use crate::config::{read_credentials, read_user_id};
+use crate::prompt::is_interactive;
+use crate::GlobalArgs;
+use clap::Args;
 use colored::Colorize;
-use reqwest::blocking::Client;
-use reqwest::header;
 use std::io::{self, Write};
+use umsebenzi::client::UmsebenziClient;
+use umsebenzi::error::ApiError;
+use umsebenzi::filter::{TaskFilter, CURRENT_USER};
+use umsebenzi::page::{Listing, Pages};
 
-pub const CLIENT_ERROR: &str = "Unable to create request client";
-pub const CLIENT_RESPONSE_ERROR: &str = "Response Error";
 pub const PROJECT_DELETE_CONFIRMATION: &str = "Are you sure you want to delete project with ID=";
 pub const TASK_DELETE_CONFIRMATION: &str =
     "Are you sure you want to delete task, including subtasks with code=";
 
+// Pages followed by list commands unless --all is given
+const DEFAULT_MAX_PAGES: usize = 10;
+
+// How much of a paginated list to fetch
+#[derive(Args, Debug)]
+pub struct PageArgs {
+    /// Show at most this many items
+    #[arg(long, conflicts_with = "all")]
+    limit: Option<usize>,
+    /// Fetch only this page
+    #[arg(long, conflicts_with = "all")]
+    page: Option<u32>,
+    /// Fetch every page
+    #[arg(long)]
+    all: bool,
+}
+
+impl PageArgs {
+    pub fn pages(&self) -> Pages {
+        Pages {
+            page: self.page,
+            limit: self.limit,
+            max_pages: if self.all {
+                None
+            } else {
+                Some(DEFAULT_MAX_PAGES)
+            },
+        }
+    }
+
+    // Only part of the list is asked for
+    pub fn is_partial(&self) -> bool {
+        self.page.is_some() || self.limit.is_some()
+    }
+}
+
+// Lists cut short by the page cap are never shown without saying so
+pub fn warn_truncated<T>(listing: &Listing<T>, name: &str) {
+    if listing.next.is_none() {
+        return;
+    }
+    // The server total includes items filtered out client side
+    let total = match listing.count {
+        Some(c) if !listing.filtered => format!(" of {c}"),
+        _ => String::new(),
+    };
+    eprintln!(
+        "{}: showing the first {}{total} {name}, use --all to fetch every page",
+        "Warning".yellow().bold(),
+        listing.items.len(),
+    );
+}
+
+#[allow(clippy::upper_case_acronyms)]
 pub enum RequestType {
     PROJECT,
     TASK,
@@ -26,100 +83,96 @@ impl RequestType {
     }
 }
 
-pub struct RequestClient {
-    pub client: Client,
-    pub url: String,
-}
-
-fn get_headers(token: &str) -> Result<header::HeaderMap> {
-    let mut auth = String::from("Token ");
-    auth.push_str(token);
-
-    let mut headers = header::HeaderMap::new();
-    headers.insert(header::AUTHORIZATION, header::HeaderValue::from_str(&auth)?);
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("application/json"),
-    );
-    headers.insert(
-        header::ACCEPT,
-        header::HeaderValue::from_static("application/json"),
-    );
-    Ok(headers)
-}
-
-fn build_url(conf: &Data, endpoint: &str, instance: Option<&String>) -> Result<String> {
-    let mut api_url = String::new();
-    api_url.push_str(&conf.host);
-    api_url.push_str(endpoint);
-    if let Some(id) = instance{
-        api_url.push_str("/");
-        api_url.push_str(&id);
+pub fn get_client(global: &GlobalArgs) -> Result<UmsebenziClient> {
+    if global.offline {
+        return Err(CliError::Offline(
+            "this command needs the API, run it without --offline".to_string(),
+        ));
     }
-    
-    Ok(api_url)
+    let config = read_credentials(global).map_err(|err| CliError::Config(err.to_string()))?;
+    Ok(UmsebenziClient::new(&config.host, &config.token)?)
 }
 
-fn get_client_builder(config: &Data) -> Result<Client> {
-    let headers = get_headers(&config.credentials)?;
-    let client = Client::builder().default_headers(headers).build()?;
-    Ok(client)
+// The user_id of the profile, otherwise the user the token belongs to,
+// which is cached for --offline. Pass the client when there is one so the
+// credentials aren't read again
+pub fn current_user(global: &GlobalArgs, client: Option<&UmsebenziClient>) -> Result<String> {
+    if let Some(id) = read_user_id(global) {
+        return Ok(id);
+    }
+    if global.offline {
+        return Cache::open(global)?.user().ok_or_else(|| {
+            CliError::Offline(
+                "your user ID isn't cached, set it with config add --user-id".to_string(),
+            )
+        });
+    }
+    let me = match client {
+        Some(c) => c.me()?,
+        None => get_client(global)?.me()?,
+    };
+    let id = me.id.to_string();
+    if let Some(c) = cache(global) {
+        c.save_user(&id);
+    }
+    Ok(id)
 }
 
-pub fn prepare_client(endpoint: &str, instance: Option<&String>) -> Result<RequestClient> {
-    let config = match read_toml_file() {
-        Ok(v) => v,
-        Err(err) => {
-            eprintln!("{}: {err}", "Unable to read toml file".red());
-            std::process::exit(1);
-        }
-    };
-    let url = match build_url(&config, endpoint, instance) {
-        Ok(u) => u,
-        Err(err) => {
-            eprintln!("{}: {err}", "Unable to build url".red());
-            std::process::exit(1);
-        }
-    };
-    let client = match get_client_builder(&config) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
-        }
-    };
-    Ok(RequestClient {
-        client: client,
-        url: url,
-    })
+// "me" in the assignee filter is replaced with the current user ID so it is
+// also checked client side
+pub fn resolve_assignee(
+    filter: &mut TaskFilter,
+    global: &GlobalArgs,
+    client: Option<&UmsebenziClient>,
+) -> Result<()> {
+    if filter.assignee.as_deref() == Some(CURRENT_USER) {
+        filter.assignee = Some(current_user(global, client)?);
+    }
+    Ok(())
 }
 
-pub fn get_request(endpoint: &str, instance: Option<&String>) -> Result<RequestClient> {
-    let prep = prepare_client(endpoint, instance)?;
-    Ok(prep)
+pub fn delete_confirmation(item_id: &String, request: RequestType) -> Result<bool> {
+    confirm(&format!("{} {}", request.value().red().bold(), item_id))
 }
 
-pub fn delete_confirmation(item_id: &String, request: RequestType) -> bool {
+// Only an exact Y or N answer is accepted
+pub fn confirm(question: &str) -> Result<bool> {
     let yes = "Y";
     let no = "N";
-    print!("{} {} [Y/N]: ", request.value().red().bold(), item_id);
-    let _ = io::stdout().flush();
+    print!("{question} [Y/N]: ");
+    io::stdout().flush()?;
     let mut confirm_buf = String::new();
-    io::stdin()
-        .read_line(&mut confirm_buf)
-        .expect("Expected Y or N");
+    io::stdin().read_line(&mut confirm_buf)?;
     let input = confirm_buf.trim();
     if input.is_empty() {
-        eprintln!("Expected Y or N");
-        std::process::exit(1);
+        return Err(CliError::input("Expected Y or N"));
     } else if input != yes && input != no {
-        eprintln!("Options are Y or N");
-        std::process::exit(1);
+        return Err(CliError::input("Options are Y or N"));
     }
-    if input.eq(yes) {
-        return true;
+    Ok(input.eq(yes))
+}
+
+// Send the request, when run interactively the fields rejected by the API
+// are asked for again and the request retried instead of failing
+pub fn send_with_retry<R, T>(
+    request: &mut R,
+    send: impl Fn(&R) -> std::result::Result<T, ApiError>,
+    reprompt: impl Fn(&mut R, &str) -> Result<()>,
+) -> Result<T> {
+    loop {
+        let err = match send(request) {
+            Ok(v) => return Ok(v),
+            Err(err) => err,
+        };
+        let fields = err.rejected_fields();
+        if fields.is_empty() || !is_interactive() {
+            return Err(err.into());
+        }
+        eprintln!("{}", CliError::from(err));
+        for field in fields {
+            reprompt(request, field)?;
+        }
     }
-    false
 }
 
 #[cfg(test)]
@@ -128,12 +181,17 @@ mod tests {
 
     #[test]
     fn request_client() {
-        let item_id = "1".to_string();
-        let request = get_request("/endpoint", Some(&item_id));
-        assert!(request.is_ok());
+        let global = GlobalArgs {
+            output: Default::default(),
+            profile: None,
+            host: Some("http://localhost:8000/api/v1".to_string()),
+            token: Some("example".to_string()),
+            offline: false,
+        };
+        let client = get_client(&global).unwrap();
         assert_eq!(
-            request.is_ok_and(|r| r.url == "http://localhost:8000/api/v1/endpoint/1"),
-            true
+            client.url("/endpoint", Some("1")),
+            "http://localhost:8000/api/v1/endpoint/1"
         );
     }
 }
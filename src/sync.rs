@@ -0,0 +1,623 @@
+use chrono::{DateTime, Local};
+use clap::{Parser, ValueEnum};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+use umsebenzi::client::UmsebenziClient;
+use umsebenzi::enums::Status;
+use umsebenzi::error::ApiError;
+use umsebenzi::request::TaskRequest;
+use umsebenzi::response::TaskResponse;
+
+use crate::bulk::TaskResult;
+use crate::cache::{cache, Cache};
+use crate::cli_error::{CliError, Result};
+use crate::description::text_editor;
+use crate::output::print_list;
+use crate::prompt::{input, is_interactive};
+use crate::service::get_client;
+use crate::task::update_request;
+use crate::GlobalArgs;
+
+const QUEUE_FILE: &str = "queue.json";
+
+#[derive(Parser, Debug)]
+pub struct SyncArgs {
+    /// List the queued changes with their number without sending them
+    #[arg(long, conflicts_with = "drop")]
+    list: bool,
+    /// Remove the queued change with this number from --list
+    #[arg(long, value_name = "N")]
+    drop: Option<usize>,
+    /// Resolve every conflict this way instead of asking
+    #[arg(long, value_enum)]
+    resolve: Option<Resolution>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum Resolution {
+    /// Send the queued change over the server changes
+    Mine,
+    /// Drop the queued change
+    Server,
+    /// Merge both in the editor
+    Merge,
+}
+
+// Task change made with --offline. modified_at is the task's modified_at
+// when the change was made, a different one on the server is a conflict
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    Add {
+        task: TaskRequest,
+    },
+    Status {
+        code: String,
+        status: Status,
+        modified_at: Option<String>,
+    },
+    Edit {
+        code: String,
+        task: TaskRequest,
+        modified_at: Option<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Queued {
+    pub queued_at: DateTime<Local>,
+    pub change: Change,
+}
+
+// Queued change as shown by --list, number is what --drop takes
+#[derive(Serialize)]
+struct Listed<'a> {
+    number: usize,
+    #[serde(flatten)]
+    queued: &'a Queued,
+}
+
+impl Change {
+    fn name(&self) -> String {
+        match self {
+            Change::Add { task } => format!("add '{}'", task.title),
+            Change::Status { code, status, .. } => format!("{code} status {status:?}"),
+            Change::Edit { code, .. } => format!("{code} edit"),
+        }
+    }
+
+    fn code(&self) -> Option<&str> {
+        match self {
+            Change::Add { .. } => None,
+            Change::Status { code, .. } | Change::Edit { code, .. } => Some(code),
+        }
+    }
+
+    fn modified_at(&self) -> Option<&str> {
+        match self {
+            Change::Add { .. } => None,
+            Change::Status { modified_at, .. } | Change::Edit { modified_at, .. } => {
+                modified_at.as_deref()
+            }
+        }
+    }
+
+    // A change queued after another change of the same task was made against
+    // the same copy, once the earlier one is sent it is based on the new copy
+    fn rebase(&mut self, sent: &HashMap<String, (String, String)>) {
+        if let Change::Status {
+            code, modified_at, ..
+        }
+        | Change::Edit {
+            code, modified_at, ..
+        } = self
+        {
+            if let (Some(m), Some((before, after))) = (modified_at.as_mut(), sent.get(code)) {
+                if m == before {
+                    *m = after.clone();
+                }
+            }
+        }
+    }
+}
+
+// Changes waiting to be sent, kept next to the cache of the host. Unlike the
+// cache, failing to read or write the queue stops the command
+pub struct Queue {
+    path: PathBuf,
+}
+
+impl Queue {
+    pub fn open(global: &GlobalArgs) -> Result<Queue> {
+        Ok(Queue {
+            path: Cache::open(global)?.path(QUEUE_FILE),
+        })
+    }
+
+    pub fn load(&self) -> Result<Vec<Queued>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let text = fs::read_to_string(&self.path)?;
+        serde_json::from_str(&text).map_err(|err| {
+            CliError::Config(format!("Unable to read {}: {err}", self.path.display()))
+        })
+    }
+
+    fn save(&self, queue: &[Queued]) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text =
+            serde_json::to_string_pretty(queue).map_err(|err| CliError::Config(err.to_string()))?;
+        fs::write(&self.path, text)?;
+        Ok(())
+    }
+
+    pub fn push(&self, change: Change) -> Result<()> {
+        let mut queue = self.load()?;
+        queue.push(Queued {
+            queued_at: Local::now(),
+            change,
+        });
+        self.save(&queue)
+    }
+}
+
+// Sends task changes to the API and keeps the cache up to date, or queues
+// them for `sync` with --offline
+pub enum TaskWriter {
+    Api(UmsebenziClient, Option<Cache>),
+    Queue(Queue),
+}
+
+impl TaskWriter {
+    pub fn new(global: &GlobalArgs) -> Result<TaskWriter> {
+        if global.offline {
+            Ok(TaskWriter::Queue(Queue::open(global)?))
+        } else {
+            Ok(TaskWriter::Api(get_client(global)?, cache(global)))
+        }
+    }
+
+    pub fn is_queue(&self) -> bool {
+        matches!(self, TaskWriter::Queue(_))
+    }
+
+    pub fn update_status(&self, task: &TaskResponse, status: Status) -> Result<()> {
+        match self {
+            TaskWriter::Api(client, cache) => {
+                client.update_status(&task.code, status)?;
+                if let Some(c) = cache {
+                    c.save_written_task(client, &task.code, None);
+                }
+                Ok(())
+            }
+            TaskWriter::Queue(queue) => queue.push(Change::Status {
+                code: task.code.clone(),
+                status,
+                modified_at: Some(task.modified_at.clone()),
+            }),
+        }
+    }
+
+    pub fn update_task(&self, task: &TaskResponse, request: &TaskRequest) -> Result<()> {
+        match self {
+            TaskWriter::Api(client, cache) => {
+                let updated = client.update_task(&task.code, request)?;
+                if let Some(c) = cache {
+                    c.save_written_task(client, &task.code, Some(updated));
+                }
+                Ok(())
+            }
+            TaskWriter::Queue(queue) => queue.push(Change::Edit {
+                code: task.code.clone(),
+                task: request.clone(),
+                modified_at: Some(task.modified_at.clone()),
+            }),
+        }
+    }
+}
+
+pub fn print_queued(global: &GlobalArgs) {
+    if global.output.is_table() {
+        println!(
+            "{}, run `umsebenzi sync` once online",
+            "Change queued".yellow().bold()
+        );
+    }
+}
+
+fn is_conflict(modified_at: &Option<String>, server: &TaskResponse) -> bool {
+    modified_at
+        .as_deref()
+        .is_some_and(|m| m != server.modified_at)
+}
+
+// Fields of the two requests that differ, as toml values
+fn changed_fields(mine: &TaskRequest, server: &TaskRequest) -> Result<Vec<String>> {
+    let mine = to_table(mine)?;
+    let server = to_table(server)?;
+    let mut keys: Vec<&String> = server.keys().collect();
+    keys.extend(mine.keys().filter(|k| !server.contains_key(*k)));
+    Ok(keys
+        .into_iter()
+        .filter(|k| mine.get(*k) != server.get(*k))
+        .cloned()
+        .collect())
+}
+
+fn to_table(task: &TaskRequest) -> Result<toml::Table> {
+    toml::Table::try_from(task).map_err(|err| CliError::Config(err.to_string()))
+}
+
+// The queued request as toml, each changed field preceded by the server value
+// in a comment
+fn merge_text(mine: &TaskRequest, server: &TaskRequest) -> Result<String> {
+    let changed = changed_fields(mine, server)?;
+    let mine = to_table(mine)?;
+    let server = to_table(server)?;
+    let mut text = String::from(
+        "# Your queued change. Each field that differs shows the server value in a comment, \
+         followed by your value\n",
+    );
+    for key in server
+        .keys()
+        .chain(mine.keys().filter(|k| !server.contains_key(*k)))
+    {
+        if changed.contains(key) {
+            let value = server
+                .get(key)
+                .map_or("unset".to_string(), |v| v.to_string());
+            for line in format!("server: {key} = {value}").lines() {
+                text.push_str(&format!("# {line}\n"));
+            }
+        }
+        if let Some(value) = mine.get(key) {
+            text.push_str(&format!("{key} = {value}\n"));
+        }
+    }
+    Ok(text)
+}
+
+fn ask(server: &TaskResponse, changed: &[String]) -> Result<Resolution> {
+    if !is_interactive() {
+        return Err(CliError::input(format!(
+            "{} changed on the server since the change was queued, use --resolve",
+            server.code
+        )));
+    }
+    println!(
+        "{} changed on the server at {}, fields: {}",
+        server.code.bold(),
+        server.modified_at,
+        changed.join(", ")
+    );
+    loop {
+        match input("Keep [m]ine, keep [s]erver or [e]dit a merge")?.as_str() {
+            "m" => return Ok(Resolution::Mine),
+            "s" => return Ok(Resolution::Server),
+            "e" => return Ok(Resolution::Merge),
+            _ => {}
+        }
+    }
+}
+
+// What replay did with a change. Only a change sent as queued, without a
+// conflict, is a base later changes of the task can be rebased on
+struct Replayed {
+    done: &'static str,
+    conflict: bool,
+}
+
+impl Replayed {
+    fn sent(done: &'static str) -> Replayed {
+        Replayed {
+            done,
+            conflict: false,
+        }
+    }
+}
+
+// Send a change made against an older copy of the task
+fn resolve(
+    client: &UmsebenziClient,
+    server: &TaskResponse,
+    mine: TaskRequest,
+    resolution: Option<Resolution>,
+) -> Result<Replayed> {
+    let current = update_request(server)?;
+    let changed = changed_fields(&mine, &current)?;
+    let resolution = match resolution {
+        Some(r) => r,
+        None => ask(server, &changed)?,
+    };
+    let done = match resolution {
+        Resolution::Mine => {
+            client.update_task(&server.code, &mine)?;
+            "kept mine"
+        }
+        Resolution::Server => "kept server",
+        Resolution::Merge => {
+            let text = text_editor(Some(merge_text(&mine, &current)?))?;
+            let merged: TaskRequest = toml::from_str(&text)
+                .map_err(|err| CliError::input(format!("Merged task is not valid: {err}")))?;
+            client.update_task(&server.code, &merged)?;
+            "merged"
+        }
+    };
+    Ok(Replayed {
+        done,
+        conflict: true,
+    })
+}
+
+fn replay(
+    client: &UmsebenziClient,
+    change: &Change,
+    resolution: Option<Resolution>,
+) -> Result<Replayed> {
+    match change {
+        Change::Add { task } => {
+            client.create_task(task)?;
+            Ok(Replayed::sent("created"))
+        }
+        Change::Status {
+            code,
+            status,
+            modified_at,
+        } => {
+            let server = client.get_task(code)?;
+            if !is_conflict(modified_at, &server) {
+                client.update_status(code, *status)?;
+                return Ok(Replayed::sent("status updated"));
+            }
+            let mine = TaskRequest {
+                status: status.to_value(),
+                ..update_request(&server)?
+            };
+            resolve(client, &server, mine, resolution)
+        }
+        Change::Edit {
+            code,
+            task,
+            modified_at,
+        } => {
+            let server = client.get_task(code)?;
+            if !is_conflict(modified_at, &server) {
+                client.update_task(code, task)?;
+                return Ok(Replayed::sent("updated"));
+            }
+            resolve(client, &server, task.clone(), resolution)
+        }
+    }
+}
+
+fn list(queued: &[Queued], global: &GlobalArgs) -> Result<()> {
+    if !global.output.is_table() {
+        let listed: Vec<Listed> = queued
+            .iter()
+            .enumerate()
+            .map(|(i, queued)| Listed {
+                number: i + 1,
+                queued,
+            })
+            .collect();
+        return print_list(&listed, global.output);
+    }
+    for (i, q) in queued.iter().enumerate() {
+        println!(
+            "{:>3}  {}  {}",
+            i + 1,
+            q.queued_at.format("%Y-%m-%d %H:%M"),
+            q.change.name()
+        );
+    }
+    Ok(())
+}
+
+// Changes the API keeps rejecting would otherwise be sent on every sync
+fn drop_change(queue: &Queue, mut queued: Vec<Queued>, number: usize) -> Result<()> {
+    if number == 0 || number > queued.len() {
+        return Err(CliError::input(format!(
+            "No queued change {number}, see `umsebenzi sync --list`"
+        )));
+    }
+    let dropped = queued.remove(number - 1);
+    queue.save(&queued)?;
+    println!("{} {}", "Dropped".yellow().bold(), dropped.change.name());
+    Ok(())
+}
+
+// The fresh copy of a task is the base of its later changes only when the
+// change was sent as queued. After a conflict the later changes were still
+// made against the old copy and are checked against the server again
+fn record_sent(
+    sent: &mut HashMap<String, (String, String)>,
+    change: &Change,
+    replayed: &Replayed,
+    task: &TaskResponse,
+) {
+    if replayed.conflict {
+        return;
+    }
+    if let Some(m) = change.modified_at() {
+        sent.insert(task.code.clone(), (m.to_string(), task.modified_at.clone()));
+    }
+}
+
+// Send the queued changes in order, failed ones stay queued. Later changes of
+// a task whose change failed are kept back, and the run stops when the API
+// can't be reached
+pub fn run(args: SyncArgs, global: &GlobalArgs) -> Result<()> {
+    let queue = Queue::open(global)?;
+    let queued = queue.load()?;
+    if args.list {
+        return list(&queued, global);
+    }
+    if let Some(number) = args.drop {
+        return drop_change(&queue, queued, number);
+    }
+    if queued.is_empty() {
+        if global.output.is_table() {
+            println!("Nothing to sync");
+        }
+        return Ok(());
+    }
+    let client = get_client(global)?;
+    let cache = cache(global);
+    let mut failed: Vec<Queued> = Vec::new();
+    let mut results = Vec::new();
+    // modified_at of each sent task before and after its changes
+    let mut sent: HashMap<String, (String, String)> = HashMap::new();
+    // Tasks with a change that failed
+    let mut held: HashSet<String> = HashSet::new();
+    let mut left: Vec<Queued> = queued.clone();
+    for (i, q) in queued.iter().enumerate() {
+        let name = q.change.name();
+        let mut change = q.change.clone();
+        change.rebase(&sent);
+        let replayed = match change.code().filter(|c| held.contains(*c)) {
+            Some(code) => Err(CliError::input(format!(
+                "kept back, an earlier change of {code} failed"
+            ))),
+            None => replay(&client, &change, args.resolve),
+        };
+        let unreachable = matches!(replayed, Err(CliError::Api(ApiError::Network(_))));
+        let result = match replayed {
+            Ok(replayed) => {
+                // The fresh copy goes in the cache
+                if let Some(code) = change.code() {
+                    if let Ok(task) = client.get_task(code) {
+                        record_sent(&mut sent, &q.change, &replayed, &task);
+                        if let Some(c) = &cache {
+                            c.save_tasks(std::slice::from_ref(&task), false);
+                        }
+                    }
+                }
+                if global.output.is_table() {
+                    println!("{}: {}", name.bold(), replayed.done.green());
+                }
+                TaskResult {
+                    code: name,
+                    ok: true,
+                    error: None,
+                }
+            }
+            Err(err) => {
+                eprintln!("{}: {err}", name.bold());
+                if let Some(code) = change.code() {
+                    held.insert(code.to_string());
+                }
+                failed.push(q.clone());
+                TaskResult {
+                    code: name,
+                    ok: false,
+                    error: Some(err.to_string()),
+                }
+            }
+        };
+        results.push(result);
+        // Saved after every change so an interrupted sync doesn't send one twice
+        left = failed.iter().chain(&queued[i + 1..]).cloned().collect();
+        queue.save(&left)?;
+        if unreachable {
+            eprintln!("{}: the API can't be reached", "Sync stopped".red().bold());
+            break;
+        }
+    }
+    if !global.output.is_table() {
+        print_list(&results, global.output)?;
+    }
+    if !left.is_empty() {
+        return Err(CliError::Partial(format!(
+            "{} of {} changes not synced, they stay queued. \
+             See them with `umsebenzi sync --list`, remove one with `umsebenzi sync --drop <N>`",
+            left.len(),
+            queued.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn request(title: &str, due_date: Option<&str>) -> TaskRequest {
+        TaskRequest {
+            project_id: 1,
+            title: title.to_string(),
+            description: "First line\nSecond line".to_string(),
+            status: 3,
+            issue: 1,
+            due_date: due_date.map(str::to_string),
+            assigned_to_id: "1".to_string(),
+            parent_id: None,
+        }
+    }
+
+    #[test]
+    fn merge_shows_server_values() {
+        let mine = request("Mine", None);
+        let server = request("Server", Some("2026-05-01"));
+        assert_eq!(
+            changed_fields(&mine, &server).unwrap(),
+            ["due_date", "title"]
+        );
+        let text = merge_text(&mine, &server).unwrap();
+        assert!(text.starts_with("# Your queued change. Each field that differs shows the server"));
+        assert!(text.contains("# server: title = \"Server\"\ntitle = \"Mine\"\n"));
+        assert!(text.contains("# server: due_date = \"2026-05-01\"\n"));
+        // Unchanged the text parses back to the queued request
+        let merged: TaskRequest = toml::from_str(&text).unwrap();
+        assert_eq!(merged.title, "Mine");
+        assert_eq!(merged.description, mine.description);
+        assert_eq!(merged.due_date, None);
+    }
+
+    #[test]
+    fn later_changes_after_a_conflict_are_checked_again() {
+        let edit = |title| Change::Edit {
+            code: "WEB-1".to_string(),
+            task: request(title, None),
+            modified_at: Some("2026-01-02".to_string()),
+        };
+        let rebased = |conflict| {
+            let mut sent = HashMap::new();
+            // Changed on the server since both edits were queued
+            let server: TaskResponse = fixtures::task(1).set("modified_at", "2026-01-05").build();
+            let replayed = Replayed {
+                done: "kept server",
+                conflict,
+            };
+            record_sent(&mut sent, &edit("First"), &replayed, &server);
+            let mut second = edit("Second");
+            second.rebase(&sent);
+            second.modified_at().map(str::to_string)
+        };
+        // The first edit conflicted, the second is still based on the old copy
+        assert_eq!(rebased(true).as_deref(), Some("2026-01-02"));
+        assert_eq!(rebased(false).as_deref(), Some("2026-01-05"));
+    }
+
+    #[test]
+    fn queue_round_trips() {
+        let queued = vec![Queued {
+            queued_at: Local::now(),
+            change: Change::Status {
+                code: "WEB-1".to_string(),
+                status: Status::REVIEW,
+                modified_at: Some("2026-01-02".to_string()),
+            },
+        }];
+        let text = serde_json::to_string(&queued).unwrap();
+        assert!(text.contains("\"kind\":\"status\""));
+        let read: Vec<Queued> = serde_json::from_str(&text).unwrap();
+        assert_eq!(read[0].change.name(), "WEB-1 status REVIEW");
+    }
+}
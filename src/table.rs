@@ -0,0 +1,274 @@
+use clap::Args;
+use cli_table::{print_stdout, Cell, Style, Table};
+use std::cmp::Ordering;
+
+use crate::cli_error::{CliError, Result};
+use umsebenzi::response::{ProjectResponse, TaskResponse};
+
+// Value a column is sorted by, numbers and statuses keep their natural order
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+pub enum SortKey {
+    Number(i64),
+    Text(String),
+}
+
+// Rows of a list table with selectable columns
+pub trait Columns {
+    const ALL: &'static [&'static str];
+    const DEFAULT: &'static [&'static str];
+
+    fn cell(&self, column: &str) -> String;
+    fn sort_key(&self, column: &str) -> Option<SortKey>;
+}
+
+#[derive(Args, Debug)]
+pub struct TableArgs {
+    /// Columns to sort by, prefix with '-' for descending eg: due_date,-created_at
+    #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+    sort: Vec<String>,
+    /// Table columns eg: code,title,status,assigned_to,project,due_date
+    #[arg(long, value_delimiter = ',')]
+    columns: Vec<String>,
+}
+
+fn check_column<T: Columns>(column: &str) -> Result<()> {
+    if T::ALL.contains(&column) {
+        Ok(())
+    } else {
+        Err(CliError::input(format!(
+            "Unknown column '{column}', expected one of: {}",
+            T::ALL.join(", ")
+        )))
+    }
+}
+
+impl TableArgs {
+    // --columns, then the config file columns, then the defaults
+    pub fn columns<T: Columns>(&self, configured: Option<Vec<String>>) -> Result<Vec<String>> {
+        let columns = if !self.columns.is_empty() {
+            self.columns.clone()
+        } else if let Some(c) = configured.filter(|c| !c.is_empty()) {
+            c
+        } else {
+            T::DEFAULT.iter().map(|c| c.to_string()).collect()
+        };
+        for c in &columns {
+            check_column::<T>(c)?;
+        }
+        Ok(columns)
+    }
+
+    // Checks --sort and --columns, done before anything is fetched
+    pub fn check<T: Columns>(&self) -> Result<()> {
+        self.sort_keys::<T>()?;
+        for c in &self.columns {
+            check_column::<T>(c)?;
+        }
+        Ok(())
+    }
+
+    fn sort_keys<T: Columns>(&self) -> Result<Vec<(&str, bool)>> {
+        let mut keys = Vec::new();
+        for spec in &self.sort {
+            let (column, descending) = match spec.strip_prefix('-') {
+                Some(c) => (c, true),
+                None => (spec.as_str(), false),
+            };
+            check_column::<T>(column)?;
+            keys.push((column, descending));
+        }
+        Ok(keys)
+    }
+
+    pub fn sort<T: Columns>(&self, items: &mut [T]) -> Result<()> {
+        let keys = self.sort_keys::<T>()?;
+        items.sort_by(|a, b| {
+            for (column, descending) in &keys {
+                let order = match (a.sort_key(column), b.sort_key(column)) {
+                    (Some(x), Some(y)) if *descending => y.cmp(&x),
+                    (Some(x), Some(y)) => x.cmp(&y),
+                    // Rows without a value always go last
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                };
+                if order != Ordering::Equal {
+                    return order;
+                }
+            }
+            Ordering::Equal
+        });
+        Ok(())
+    }
+}
+
+pub fn print_table<T: Columns>(items: &[T], columns: &[String]) -> Result<()> {
+    let rows: Vec<Vec<_>> = items
+        .iter()
+        .map(|item| columns.iter().map(|c| item.cell(c).cell()).collect())
+        .collect();
+    let title: Vec<_> = columns.iter().map(|c| c.cell().bold(true)).collect();
+    print_stdout(rows.table().title(title))?;
+    Ok(())
+}
+
+fn text(value: &str) -> Option<SortKey> {
+    Some(SortKey::Text(value.to_lowercase()))
+}
+
+impl Columns for TaskResponse {
+    const ALL: &'static [&'static str] = &[
+        "id",
+        "code",
+        "title",
+        "issue",
+        "status",
+        "project",
+        "assigned_to",
+        "created_by",
+        "due_date",
+        "subtasks",
+        "parent",
+        "created_at",
+        "modified_at",
+    ];
+    const DEFAULT: &'static [&'static str] = &[
+        "id",
+        "title",
+        "code",
+        "issue",
+        "status",
+        "due_date",
+        "subtasks",
+        "created_at",
+    ];
+
+    fn cell(&self, column: &str) -> String {
+        match column {
+            "id" => self.id.to_string(),
+            "code" => self.code.clone(),
+            "title" => self.title.clone(),
+            "issue" => self.issue.clone(),
+            "status" => self.status.to_string(),
+            "project" => self.project.code.clone(),
+            "assigned_to" => self.assigned_to.to_string(),
+            "created_by" => self.created_by.to_string(),
+            "due_date" => self.due_date.clone().unwrap_or("N/A".to_string()),
+            "subtasks" => match &self.subtasks {
+                Some(s) if s.is_empty() => "No".to_string(),
+                Some(_) => "Yes".to_string(),
+                None => "----".to_string(),
+            },
+            "parent" => self.parent.map(|p| p.to_string()).unwrap_or_default(),
+            "created_at" => self.created_at.clone(),
+            "modified_at" => self.modified_at.clone(),
+            _ => String::new(),
+        }
+    }
+
+    fn sort_key(&self, column: &str) -> Option<SortKey> {
+        match column {
+            "id" => Some(SortKey::Number(self.id.into())),
+            "code" => text(&self.code),
+            "title" => text(&self.title),
+            "issue" => text(&self.issue),
+            "status" => Some(SortKey::Number(self.status.to_value().into())),
+            "project" => text(&self.project.code),
+            "assigned_to" => text(&self.assigned_to.email),
+            "created_by" => text(&self.created_by.email),
+            "due_date" => self.due_date.as_deref().and_then(text),
+            "subtasks" => Some(SortKey::Number(
+                self.subtasks.as_ref().map_or(0, |s| s.len() as i64),
+            )),
+            "parent" => self.parent.map(|p| SortKey::Number(p.into())),
+            "created_at" => text(&self.created_at),
+            "modified_at" => text(&self.modified_at),
+            _ => None,
+        }
+    }
+}
+
+impl Columns for ProjectResponse {
+    const ALL: &'static [&'static str] = &[
+        "id",
+        "code",
+        "title",
+        "created_by",
+        "created_at",
+        "modified_at",
+    ];
+    const DEFAULT: &'static [&'static str] = &["id", "created_by", "title", "code", "created_at"];
+
+    fn cell(&self, column: &str) -> String {
+        match column {
+            "id" => self.id.to_string(),
+            "code" => self.code.clone(),
+            "title" => self.title.clone(),
+            "created_by" => self.created_by.to_string(),
+            "created_at" => self.created_at.clone(),
+            "modified_at" => self.modified_at.clone(),
+            _ => String::new(),
+        }
+    }
+
+    fn sort_key(&self, column: &str) -> Option<SortKey> {
+        match column {
+            "id" => Some(SortKey::Number(self.id.into())),
+            "code" => text(&self.code),
+            "title" => text(&self.title),
+            "created_by" => text(&self.created_by.email),
+            "created_at" => text(&self.created_at),
+            "modified_at" => text(&self.modified_at),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn project(id: i32, code: &str) -> ProjectResponse {
+        fixtures::project(id)
+            .set("title", code)
+            .set("code", code)
+            .set("created_at", format!("2026-01-0{id}"))
+            .build()
+    }
+
+    fn args(sort: &[&str], columns: &[&str]) -> TableArgs {
+        TableArgs {
+            sort: sort.iter().map(|s| s.to_string()).collect(),
+            columns: columns.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn sorts_by_several_columns() {
+        let mut items = vec![project(1, "B"), project(2, "A"), project(3, "B")];
+        args(&["code", "-created_at"], &[])
+            .sort(&mut items)
+            .unwrap();
+        let ids: Vec<i32> = items.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn columns_are_checked() {
+        let configured = Some(vec!["code".to_string()]);
+        let columns = args(&[], &[])
+            .columns::<ProjectResponse>(configured)
+            .unwrap();
+        assert_eq!(columns, vec!["code"]);
+        assert!(args(&[], &["code", "status"])
+            .columns::<ProjectResponse>(None)
+            .is_err());
+        assert!(args(&["-nope"], &[]).sort(&mut [project(1, "A")]).is_err());
+        assert!(args(&["-nope"], &[]).check::<ProjectResponse>().is_err());
+        assert!(args(&[], &["status"]).check::<ProjectResponse>().is_err());
+        assert!(args(&["-code"], &["code"])
+            .check::<ProjectResponse>()
+            .is_ok());
+    }
+}
@@ -1,19 +1,30 @@
 use clap::{Parser, Subcommand};
 
+use crate::bulk::{run_each, TaskSelection};
+use crate::cache::{cache, offline_task, offline_tasks, Cache};
+use crate::cli_error::{CliError, Result};
+use crate::config::read_config_file;
 use crate::defaults::{show_issue_options, show_status_options};
-use crate::description::text_editor;
-use crate::enums::{Issue, Status};
-use crate::request::TaskRequest;
-use crate::response::{TaskErrorResponse, TaskResponse, UserID};
-use crate::service::{
-    delete_confirmation, get_request, RequestType, CLIENT_ERROR, CLIENT_RESPONSE_ERROR,
-};
+use crate::description::{read_description_file, text_editor};
+use crate::output::{print_item, print_list};
+use crate::prompt::{input, is_interactive, require_interactive, required_input};
+use crate::service::{get_client, resolve_assignee, send_with_retry, warn_truncated, PageArgs};
+use crate::sync::{print_queued, Change, Queue, TaskWriter};
+use crate::table::{print_table, TableArgs};
+use crate::tree;
+use crate::workflow::{skipped, StatusChange, Transition, Workflows};
+use crate::GlobalArgs;
+use chrono::NaiveDate;
 use cli_table::{print_stdout, WithTitle};
 use colored::Colorize;
 use std::collections::HashMap;
-use std::io::{self, Write};
+use std::str::FromStr;
+use umsebenzi::client::UmsebenziClient;
+use umsebenzi::enums::{Issue, Status};
+use umsebenzi::filter::{TaskFilter, CURRENT_USER};
+use umsebenzi::request::TaskRequest;
+use umsebenzi::response::TaskResponse;
 
-const TASK_ENDPOINT: &str = "/tasks";
 const TASK_TITLE_ERROR: &str = "Task title expected";
 const TASK_DESCRIPTION_ERROR: &str = "Task description expected";
 const TASK_ISSUE_ERROR: &str = "Task issue expected";
@@ -23,24 +34,214 @@ const TASK_DATE_ERROR: &str = "Task date expected";
 const TASK_STATUS_ERROR: &str = "Task status expected";
 const TASK_ASSIGN_ERROR: &str = "Task needs to be assigned to a user";
 
-
 #[derive(Parser, Debug)]
-pub struct TaskFilterArgs{
+pub struct TaskFilterArgs {
+    /// Project code
     #[arg(short, long)]
     project: Option<String>,
-    #[arg(short, long)]
-    status: Option<String>
+    /// Statuses separated by commas eg: 3,4 or todo,wip
+    #[arg(short, long, value_delimiter = ',')]
+    status: Vec<Status>,
+    /// User ID, or 'me'
+    #[arg(long)]
+    assignee: Option<String>,
+    /// Only epics
+    #[arg(long, conflicts_with = "subtask")]
+    epic: bool,
+    /// Only subtasks
+    #[arg(long)]
+    subtask: bool,
+    /// Parent task ID
+    #[arg(long)]
+    parent: Option<i32>,
+    /// Due before YYYY-MM-DD
+    #[arg(long)]
+    due_before: Option<NaiveDate>,
+    /// Due after YYYY-MM-DD
+    #[arg(long)]
+    due_after: Option<NaiveDate>,
+    /// Past the due date and not complete
+    #[arg(long)]
+    overdue: bool,
+    /// User ID or email of the creator
+    #[arg(long)]
+    created_by: Option<String>,
+    /// Text in the code, title or description
+    #[arg(long)]
+    search: Option<String>,
+    #[command(flatten)]
+    pages: PageArgs,
+    #[command(flatten)]
+    table: TableArgs,
+}
+
+impl TaskFilterArgs {
+    fn filter(&self) -> TaskFilter {
+        let issue = if self.epic {
+            Some(Issue::EPIC)
+        } else if self.subtask {
+            Some(Issue::SUBTASK)
+        } else {
+            None
+        };
+        TaskFilter {
+            project: self.project.clone(),
+            status: self.status.clone(),
+            issue,
+            assignee: self.assignee.clone(),
+            parent: self.parent,
+            due_before: self.due_before,
+            due_after: self.due_after,
+            overdue: self.overdue,
+            created_by: self.created_by.clone(),
+            search: self.search.clone(),
+        }
+    }
 }
 
+#[derive(Parser, Debug)]
+pub struct TaskAddArgs {
+    /// Project ID
+    #[arg(long)]
+    project: Option<i32>,
+    #[arg(long)]
+    title: Option<String>,
+    #[arg(long, conflicts_with = "description_file")]
+    description: Option<String>,
+    /// Read the description from a file, use '-' for stdin
+    #[arg(long, value_name = "FILE")]
+    description_file: Option<String>,
+    /// Issue number or name, 1=epic 2=subtask [default: epic]
+    #[arg(long)]
+    issue: Option<String>,
+    /// Parent task ID, required for subtasks
+    #[arg(long)]
+    parent: Option<i32>,
+    /// Status number or name, see `config task-status` [default: draft]
+    #[arg(long)]
+    status: Option<String>,
+    /// Due date as YYYY-MM-DD
+    #[arg(long)]
+    due: Option<String>,
+    /// User ID the task is assigned to
+    #[arg(long)]
+    assignee: Option<String>,
+}
+
+impl TaskAddArgs {
+    fn is_empty(&self) -> bool {
+        self.project.is_none()
+            && self.title.is_none()
+            && self.description.is_none()
+            && self.description_file.is_none()
+            && self.issue.is_none()
+            && self.parent.is_none()
+            && self.status.is_none()
+            && self.due.is_none()
+            && self.assignee.is_none()
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct TaskEditArgs {
+    task_codes: Vec<String>,
+    #[arg(long)]
+    title: Option<String>,
+    #[arg(long, conflicts_with = "description_file")]
+    description: Option<String>,
+    /// Read the description from a file, use '-' for stdin
+    #[arg(long, value_name = "FILE")]
+    description_file: Option<String>,
+    /// Issue number or name, 1=epic 2=subtask
+    #[arg(long)]
+    issue: Option<String>,
+    /// Parent task ID, required when changing an epic to a subtask
+    #[arg(long)]
+    parent: Option<i32>,
+    /// Status number or name, see `config task-status`
+    #[arg(long)]
+    status: Option<String>,
+    /// Due date as YYYY-MM-DD
+    #[arg(long)]
+    due: Option<String>,
+    /// User ID the task is assigned to
+    #[arg(long)]
+    assignee: Option<String>,
+    /// Ignore the workflow rules in the config file
+    #[arg(long)]
+    force: bool,
+    #[command(flatten)]
+    selection: TaskSelection,
+}
+
+impl TaskEditArgs {
+    fn is_empty(&self) -> bool {
+        self.title.is_none()
+            && self.description.is_none()
+            && self.description_file.is_none()
+            && self.issue.is_none()
+            && self.parent.is_none()
+            && self.status.is_none()
+            && self.due.is_none()
+            && self.assignee.is_none()
+    }
+}
 
 #[derive(Subcommand, Debug)]
 enum TaskCLI {
     List(TaskFilterArgs),
-    Add,
-    Detail { task_code: String },
-    Edit { task_code: String },
-    Delete { task_code: String },
-    Status { task_code: String, status: String },
+    /// Tasks assigned to you, takes the same flags as list
+    Mine(TaskFilterArgs),
+    Add(TaskAddArgs),
+    Detail {
+        task_code: String,
+    },
+    /// Epics with their subtasks indented, for one task or a project
+    Tree {
+        #[arg(conflicts_with = "project")]
+        task_code: Option<String>,
+        /// Project code
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+    /// Edit tasks, prompts for every field when a single code is given without flags
+    Edit(TaskEditArgs),
+    Delete {
+        task_codes: Vec<String>,
+        #[command(flatten)]
+        selection: TaskSelection,
+    },
+    Status {
+        /// Task codes followed by the status eg: WEB-1 WEB-2 done
+        #[arg(required = true, value_name = "TASK_CODE... STATUS")]
+        codes_and_status: Vec<String>,
+        /// Ignore the workflow rules in the config file
+        #[arg(long)]
+        force: bool,
+        #[command(flatten)]
+        selection: TaskSelection,
+    },
+    /// Move tasks to IN_PROGRESS
+    Start(TransitionArgs),
+    /// Move tasks to REVIEW
+    Review(TransitionArgs),
+    /// Move tasks to COMPLETE
+    Done(TransitionArgs),
+    /// Move tasks to ARCHIVE
+    Archive(TransitionArgs),
+    /// Move tasks to the next status
+    Next(TransitionArgs),
+    /// Move tasks to the previous status
+    Back(TransitionArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct TransitionArgs {
+    #[arg(required = true)]
+    task_codes: Vec<String>,
+    /// Ignore the workflow rules in the config file
+    #[arg(long)]
+    force: bool,
 }
 
 #[derive(Parser, Debug)]
@@ -49,517 +250,677 @@ pub struct TaskArgs {
     command: TaskCLI,
 }
 
-pub fn run(args: TaskArgs) {
+pub fn run(args: TaskArgs, global: &GlobalArgs) -> Result<()> {
     match args.command {
-        TaskCLI::Add => add(),
-        TaskCLI::List(f) => run_filter(f),
-        TaskCLI::Edit { task_code } => edit(task_code),
-        TaskCLI::Detail { task_code } => detail(task_code),
-        TaskCLI::Delete { task_code } => delete(task_code),
-        TaskCLI::Status { task_code, status } => status_update(task_code, status),
-    }
-}
-
-fn run_filter(args: TaskFilterArgs){
-    let mut filter_params = String::new();
-    if let Some(p) = args.project{
-        filter_params.push_str(&format!("?project={}", p));
-    }
-
-    if let Some(s) = args.status {
-        if filter_params.is_empty(){
-            filter_params.push_str(&format!("?status={}", s));
-        }else{
-            filter_params.push_str(&format!("&status={}", s));
+        TaskCLI::Add(a) => add(a, global),
+        TaskCLI::List(f) => run_filter(f, global),
+        TaskCLI::Mine(f) => {
+            let filter = TaskFilter {
+                assignee: Some(CURRENT_USER.to_string()),
+                ..f.filter()
+            };
+            list(&filter, &f.pages, &f.table, global)
+        }
+        TaskCLI::Edit(a) if a.is_empty() && !a.selection.is_bulk(&a.task_codes) => {
+            edit(a.task_codes[0].clone(), a.force, global)
+        }
+        TaskCLI::Edit(a) => edit_many(a, global),
+        TaskCLI::Detail { task_code } => detail(task_code, global),
+        TaskCLI::Tree { task_code, project } => tree::run(task_code, project, global),
+        TaskCLI::Delete {
+            task_codes,
+            selection,
+        } => {
+            if selection.is_bulk(&task_codes) {
+                delete_many(&task_codes, &selection, global)
+            } else {
+                delete(task_codes[0].clone(), &selection, global)
+            }
         }
-        
+        TaskCLI::Status {
+            mut codes_and_status,
+            force,
+            selection,
+        } => {
+            let status = codes_and_status.pop().unwrap_or_default();
+            let task_codes = codes_and_status;
+            if selection.is_bulk(&task_codes) {
+                status_many(&task_codes, &selection, status, force, global)
+            } else {
+                status_update(task_codes[0].clone(), status, force, global)
+            }
+        }
+        TaskCLI::Start(a) => transition(a, Transition::To(Status::IN_PROGRESS), global),
+        TaskCLI::Review(a) => transition(a, Transition::To(Status::REVIEW), global),
+        TaskCLI::Done(a) => transition(a, Transition::To(Status::COMPLETE), global),
+        TaskCLI::Archive(a) => transition(a, Transition::To(Status::ARCHIVE), global),
+        TaskCLI::Next(a) => transition(a, Transition::Next, global),
+        TaskCLI::Back(a) => transition(a, Transition::Back, global),
     }
-    list(Some(filter_params));
+}
 
+fn run_filter(args: TaskFilterArgs, global: &GlobalArgs) -> Result<()> {
+    list(&args.filter(), &args.pages, &args.table, global)
 }
 
-fn list(filter: Option<String>) {
-    let request = match get_request(TASK_ENDPOINT, None) {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red().bold());
-            std::process::exit(1)
+fn list(
+    filter: &TaskFilter,
+    pages: &PageArgs,
+    table: &TableArgs,
+    global: &GlobalArgs,
+) -> Result<()> {
+    table.check::<TaskResponse>()?;
+    let mut filter = filter.clone();
+    let mut tasks = if global.offline {
+        resolve_assignee(&mut filter, global, None)?;
+        let mut tasks = offline_tasks(global, &filter)?;
+        if let Some(limit) = pages.pages().limit {
+            tasks.truncate(limit);
         }
+        tasks
+    } else {
+        let client = get_client(global)?;
+        resolve_assignee(&mut filter, global, Some(&client))?;
+        let listing = client.list_tasks_pages(&filter, &pages.pages())?;
+        warn_truncated(&listing, "tasks");
+        if let Some(c) = cache(global) {
+            let complete = filter.is_empty() && !pages.is_partial() && listing.next.is_none();
+            c.save_tasks(&listing.items, complete);
+        }
+        listing.items
     };
-    let url;
-    if let Some(f) = filter{
-        url = request.url + &f;
-    }else{
-        url = request.url;
+    table.sort(&mut tasks)?;
+    if global.output.is_table() {
+        let configured = read_config_file().ok().and_then(|f| f.columns.task);
+        print_table(&tasks, &table.columns::<TaskResponse>(configured)?)
+    } else {
+        print_list(&tasks, global.output)
     }
+}
 
-    let resp = match request.client.get(url).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprintln!("{}: {err}", CLIENT_RESPONSE_ERROR.red().bold());
-            std::process::exit(1);
-        }
-    };
+// Fetch every matching task and cache them, or read them from the cache with --offline
+pub fn fetch_tasks(filter: &TaskFilter, global: &GlobalArgs) -> Result<Vec<TaskResponse>> {
+    if global.offline {
+        return offline_tasks(global, filter);
+    }
+    let tasks = get_client(global)?.list_tasks(filter)?;
+    if let Some(c) = cache(global) {
+        c.save_tasks(&tasks, filter.is_empty());
+    }
+    Ok(tasks)
+}
 
-    if resp.status().is_success() {
-        let proj: Vec<TaskResponse> = match resp.json() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err:?}", "Unable to parse response json".red());
-                std::process::exit(1);
-            }
-        };
-        let _ = print_stdout(proj.with_title()).is_ok();
-    } else {
-        println!("{} {}", "Error".red().bold(), resp.status());
+// Fetch a task and cache it, or read it from the cache with --offline
+pub fn fetch_task(code: &str, global: &GlobalArgs) -> Result<TaskResponse> {
+    if global.offline {
+        return offline_task(global, code);
     }
+    let task = get_client(global)?.get_task(code)?;
+    if let Some(c) = cache(global) {
+        c.save_tasks(std::slice::from_ref(&task), false);
+    }
+    Ok(task)
 }
 
-fn detail(task_code: String) {
-    let request = match get_request(TASK_ENDPOINT, Some(&task_code)) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
-        }
-    };
-    let resp = match request.client.get(request.url).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
-        }
-    };
-    if resp.status().is_success() {
-        let task: TaskResponse = match resp.json() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err}", "Unable to parse response json".red().bold());
-                std::process::exit(1);
-            }
-        };
-        println!("{}: {}", "Title".green().bold(), task.title);
-        println!("{}: {}", "Code".green().bold(), task.code);
-        println!("{}: {}", "Status".green().bold(), task.status);
-        println!("{}: {}", "Issue".green().bold(), task.issue);
-        println!(
-            "{}: {}",
-            "Due Date".green().bold(),
-            task.due_date.unwrap_or_default()
-        );
-        println!("{}: {}", "Created By".green().bold(), task.created_by);
-        println!("{}: {}", "Created At".green().bold(), task.created_at);
-        println!("");
-        println!("{}:", "Description".green().bold());
-        println!("{}", task.description);
-        println!("");
-
-        if let Some(t) = task.subtasks {
-            if !t.is_empty() {
-                println!("{}:", "Subtasks".green().bold());
-                let _ = print_stdout(t.with_title()).is_ok();
-            }
+fn detail(task_code: String, global: &GlobalArgs) -> Result<()> {
+    let task = fetch_task(&task_code, global)?;
+    if !global.output.is_table() {
+        return print_item(&task, global.output);
+    }
+    println!("{}: {}", "Title".green().bold(), task.title);
+    println!("{}: {}", "Code".green().bold(), task.code);
+    println!("{}: {}", "Status".green().bold(), task.status);
+    println!("{}: {}", "Issue".green().bold(), task.issue);
+    println!(
+        "{}: {}",
+        "Due Date".green().bold(),
+        task.due_date.unwrap_or_default()
+    );
+    println!("{}: {}", "Created By".green().bold(), task.created_by);
+    println!("{}: {}", "Created At".green().bold(), task.created_at);
+    println!();
+    println!("{}:", "Description".green().bold());
+    println!("{}", task.description);
+    println!();
+
+    if let Some(t) = task.subtasks {
+        if !t.is_empty() {
+            println!("{}:", "Subtasks".green().bold());
+            print_stdout(t.with_title())?;
         }
     }
+    Ok(())
 }
 
-fn delete(task_code: String) {
-    let is_delete = delete_confirmation(&task_code, RequestType::TASK);
+fn delete(task_code: String, selection: &TaskSelection, global: &GlobalArgs) -> Result<()> {
+    let is_delete = selection.confirm_one(&task_code)?;
     if is_delete {
-        let request = match get_request(TASK_ENDPOINT, Some(&task_code)) {
-            Ok(c) => c,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        let resp = match request.client.delete(request.url).send() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        if resp.status().is_success() {
+        let client = get_client(global)?;
+        client.delete_task(&task_code)?;
+        if let Some(c) = cache(global) {
+            c.remove_task(&task_code);
+        }
+        if global.output.is_table() {
             println!("{}", "Task Deleted".green().bold());
-        } else if resp.status().is_client_error() {
-            eprintln!("{}: Unable to delete Task:  {:?}", "Error".red(), resp);
-            std::process::exit(1);
         }
     }
+    Ok(())
 }
 
-fn add() {
-    println!("{}", "Create a new task".green().bold());
-
-    print!("{}: ", "Project [ID]".green().bold());
-    let _ = io::stdout().flush();
-    let mut project_buf = String::new();
-    io::stdin()
-        .read_line(&mut project_buf)
-        .expect(&TASK_PROJECT_ERROR.red().bold());
-    if project_buf.trim().is_empty() {
-        eprintln!("{}", TASK_PROJECT_ERROR.red().bold());
-        std::process::exit(1);
-    }
-    let proj_id = match project_buf.trim().parse::<i32>() {
-        Ok(i) => i,
-        Err(_) => {
-            eprintln!("{}", "Project ID is should be a number".red().bold());
-            std::process::exit(1);
+fn delete_many(codes: &[String], selection: &TaskSelection, global: &GlobalArgs) -> Result<()> {
+    let client = get_client(global)?;
+    let selected = selection.tasks(codes, global)?;
+    if !selection.confirm(&selected.tasks, "Delete")? {
+        return Ok(());
+    }
+    let cache = cache(global);
+    run_each(selected, global.output, "deleted", |t| {
+        client.delete_task(&t.code)?;
+        if let Some(c) = &cache {
+            c.remove_task(&t.code);
         }
-    };
+        Ok(())
+    })
+}
 
-    print!("{}: ", "Title".green().bold());
-    let _ = io::stdout().flush();
-    let mut title_buf = String::new();
-    io::stdin()
-        .read_line(&mut title_buf)
-        .expect(&TASK_TITLE_ERROR.red().bold());
-    if title_buf.trim().is_empty() {
-        eprintln!("{}", TASK_TITLE_ERROR.red().bold());
-        std::process::exit(1);
+fn add(args: TaskAddArgs, global: &GlobalArgs) -> Result<()> {
+    // Only walk through every prompt when no flags were given
+    let prompt_all = args.is_empty() && is_interactive();
+    if prompt_all {
+        println!("{}", "Create a new task".green().bold());
     }
 
-    print!(
-        "{}: ",
-        "Description [type 'Y' to open editor]".green().bold()
-    );
-    let _ = io::stdout().flush();
-    let mut answer_buf = String::new();
-    io::stdin()
-        .read_line(&mut answer_buf)
-        .expect(&TASK_DESCRIPTION_ERROR.red());
-    let mut description = String::new();
-    if answer_buf.trim() == "Y" {
-        description = text_editor(None).expect(&TASK_DESCRIPTION_ERROR.red().bold());
+    let proj_id = match args.project {
+        Some(p) => p,
+        None => {
+            require_interactive(TASK_PROJECT_ERROR, "--project")?;
+            let project = required_input("Project [ID]", TASK_PROJECT_ERROR)?;
+            project
+                .parse::<i32>()
+                .map_err(|_| CliError::input("Project ID is should be a number"))?
+        }
+    };
+
+    let title = match args.title {
+        Some(t) => t,
+        None => {
+            require_interactive(TASK_TITLE_ERROR, "--title")?;
+            required_input("Title", TASK_TITLE_ERROR)?
+        }
+    };
+
+    let description = if let Some(d) = args.description {
+        d
+    } else if let Some(path) = args.description_file {
+        read_description_file(&path)
+            .map_err(|err| CliError::input(format!("{TASK_DESCRIPTION_ERROR}: {err}")))?
     } else {
-        eprintln!("{}", "Invalid Command expected Y".red().bold());
-        std::process::exit(1);
-    }
+        require_interactive(
+            TASK_DESCRIPTION_ERROR,
+            "--description or --description-file",
+        )?;
+        let answer = input("Description [type 'Y' to open editor]")?;
+        if answer == "Y" {
+            text_editor(None)?
+        } else {
+            return Err(CliError::input("Invalid Command expected Y"));
+        }
+    };
 
-    show_issue_options();
-    print!("{}: ", "Issue [default=1]".green().bold());
-    let _ = io::stdout().flush();
-    let mut issue_buf = String::new();
-    io::stdin()
-        .read_line(&mut issue_buf)
-        .expect(&TASK_ISSUE_ERROR.red().bold());
-    if issue_buf.trim().is_empty() {
-        issue_buf = "1".to_string();
-    }
-    let issue = match Issue::from_str(&issue_buf.trim()) {
-        Ok(i) => i,
-        Err(err) => {
-            eprintln!("{}: {err}", TASK_ISSUE_ERROR.red().bold());
-            std::process::exit(1);
+    let issue_value = match args.issue {
+        Some(i) => i,
+        None if prompt_all => {
+            show_issue_options();
+            input("Issue [default=1]")?
         }
+        None => String::new(),
+    };
+    let issue = if issue_value.is_empty() && args.parent.is_some() {
+        Issue::SUBTASK
+    } else if issue_value.is_empty() {
+        Issue::EPIC
+    } else {
+        parse_issue(&issue_value)?
     };
+    if args.parent.is_some() && issue != Issue::SUBTASK {
+        return Err(CliError::input(
+            "--parent can only be used with --issue subtask",
+        ));
+    }
 
     let mut parent_id = None;
     if issue == Issue::SUBTASK {
-        print!("{}: ", "Parent Task ID".green().bold());
-        let _ = io::stdout().flush();
-        let mut parent_buf = String::new();
-        io::stdin()
-            .read_line(&mut parent_buf)
-            .expect(&TASK_PARENT_ERROR.red().bold());
-        if parent_buf.trim().is_empty() {
-            eprintln!("{}", TASK_PARENT_ERROR.red().bold());
-            std::process::exit(1);
-        }
-        match parent_buf.trim().parse::<i32>() {
-            Ok(i) => {
-                parent_id = Some(i);
-            }
-            Err(_) => {
-                eprintln!("{}", "Task parent ID is should be a number".red().bold());
-                std::process::exit(1);
+        parent_id = match args.parent {
+            Some(p) => Some(p),
+            None => {
+                require_interactive(TASK_PARENT_ERROR, "--parent")?;
+                Some(prompt_parent()?)
             }
         };
     }
-    show_status_options(false);
-    print!("{}: ", "Status [default=1]".green().bold());
-    let _ = io::stdout().flush();
-    let mut status_buf = String::new();
-    io::stdin().read_line(&mut status_buf).unwrap();
-    if status_buf.trim().is_empty() {
-        status_buf = "1".to_string();
-    }
-    let status = match Status::from_str(&status_buf.trim()) {
-        Ok(s) => s,
-        Err(err) => {
-            eprintln!("{}: {err}", TASK_STATUS_ERROR.red().bold());
-            std::process::exit(1);
+
+    let status_value = match args.status {
+        Some(s) => s,
+        None if prompt_all => {
+            show_status_options(false);
+            input("Status [default=1]")?
+        }
+        None => String::new(),
+    };
+    let status = if status_value.is_empty() {
+        Status::DRAFT
+    } else {
+        parse_status(&status_value)?
+    };
+
+    let mut due_date = args.due;
+    if due_date.is_none() && prompt_all {
+        let answer = input("Add due date [Y/N]?")?;
+        if answer.is_empty() {
+            return Err(CliError::input("answer Y or N"));
+        }
+        if answer == "Y" {
+            due_date = Some(required_input("Due Date [YYYY-MM-DD]", TASK_DATE_ERROR)?);
+        }
+    }
+
+    let assigned_to_id = match args.assignee {
+        Some(a) => a,
+        None => {
+            require_interactive(TASK_ASSIGN_ERROR, "--assignee")?;
+            required_input("Assigned To [User ID]", TASK_ASSIGN_ERROR)?
         }
     };
 
-    let mut due_date = None;
-    print!("{}: ", "Add due date [Y/N]?".green().bold());
-    let _ = io::stdout().flush();
-    let mut date_buf = String::new();
-    io::stdin()
-        .read_line(&mut date_buf)
-        .expect(&"answer Y or N".red().bold());
-    if date_buf.trim().is_empty() {
-        eprintln!("{}", &"answer Y or N".red().bold());
-        std::process::exit(1);
-    }
-    if date_buf.trim() == "Y" {
-        print!("{}: ", "Due Date [YYYY-MM-DD]".green().bold());
-        let _ = io::stdout().flush();
-        let mut due_buf = String::new();
-        io::stdin()
-            .read_line(&mut due_buf)
-            .expect(&TASK_DATE_ERROR.red().bold());
-        if due_buf.trim().is_empty() {
-            eprintln!("{}", TASK_DATE_ERROR.red().bold());
-            std::process::exit(1);
-        }
-        due_date = Some(due_buf.trim().to_string());
-    }
-
-    print!("{}: ", "Assigned To [User ID]".green().bold());
-    let _ = io::stdout().flush();
-    let mut assign_buf = String::new();
-    io::stdin()
-        .read_line(&mut assign_buf)
-        .expect(&TASK_PROJECT_ERROR.red().bold());
-    if assign_buf.trim().is_empty() {
-        eprintln!("{}", TASK_ASSIGN_ERROR.red().bold());
-        std::process::exit(1);
-    }
-
-    let task_request = TaskRequest {
+    let mut task_request = TaskRequest {
         project_id: proj_id,
-        title: title_buf.trim().to_string(),
-        description: description,
+        title,
+        description,
         status: status.to_value(),
         issue: issue.to_value(),
-        assigned_to_id: assign_buf.trim().to_string(),
-        parent_id: parent_id,
-        due_date: due_date,
+        assigned_to_id,
+        parent_id,
+        due_date,
     };
 
-    let request = match get_request(TASK_ENDPOINT, None) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
+    if global.offline {
+        Queue::open(global)?.push(Change::Add {
+            task: task_request.clone(),
+        })?;
+        print_queued(global);
+        return if global.output.is_table() {
+            Ok(())
+        } else {
+            print_item(&task_request, global.output)
+        };
+    }
+    let client = get_client(global)?;
+    let created = send_with_retry(&mut task_request, |t| client.create_task(t), reprompt_field)?;
+    if let (Some(c), Some(code)) = (cache(global), created["code"].as_str()) {
+        c.save_written_task(&client, code, Some(created.clone()));
+    }
+    if global.output.is_table() {
+        println!("{}", "task created".green().bold());
+        Ok(())
+    } else {
+        print_item(&created, global.output)
+    }
+}
+
+fn parse_issue(value: &str) -> Result<Issue> {
+    Issue::from_str(value).map_err(|err| CliError::input(format!("{TASK_ISSUE_ERROR}: {err}")))
+}
+
+fn parse_status(value: &str) -> Result<Status> {
+    Status::from_str(value).map_err(|err| CliError::input(format!("{TASK_STATUS_ERROR}: {err}")))
+}
+
+fn prompt_parent() -> Result<i32> {
+    let parent = required_input("Parent Task ID", TASK_PARENT_ERROR)?;
+    parent
+        .parse::<i32>()
+        .map_err(|_| CliError::input("Task parent ID is should be a number"))
+}
+
+// Ask again for a field rejected by the API
+fn reprompt_field(task: &mut TaskRequest, field: &str) -> Result<()> {
+    match field {
+        "project_id" => {
+            task.project_id = required_input("Project [ID]", TASK_PROJECT_ERROR)?
+                .parse::<i32>()
+                .map_err(|_| CliError::input("Project ID is should be a number"))?
         }
-    };
-    let resp = match request.client.post(request.url).json(&task_request).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
+        "title" => task.title = required_input("Title", TASK_TITLE_ERROR)?,
+        "description" => task.description = text_editor(Some(task.description.clone()))?,
+        "issue" => {
+            show_issue_options();
+            task.issue = parse_issue(&required_input("Issue", TASK_ISSUE_ERROR)?)?.to_value()
+        }
+        "parent_id" => task.parent_id = Some(prompt_parent()?),
+        "status" => {
+            show_status_options(false);
+            task.status = parse_status(&required_input("Status", TASK_STATUS_ERROR)?)?.to_value()
+        }
+        "due_date" => {
+            let due = input("Due Date [YYYY-MM-DD, leave blank for none]")?;
+            task.due_date = if due.is_empty() { None } else { Some(due) }
+        }
+        "assigned_to_id" => {
+            task.assigned_to_id = required_input("Assigned To [User ID]", TASK_ASSIGN_ERROR)?
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn status_update(
+    task_code: String,
+    status: String,
+    force: bool,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let new_status = parse_status(&status)?;
+    let writer = TaskWriter::new(global)?;
+    let workflows = Workflows::load()?;
+    // The current task is needed to check the workflow rules, and queued
+    // changes keep its modified_at to spot conflicts
+    match &writer {
+        TaskWriter::Api(client, cache) if force || workflows.is_empty() => {
+            client.update_status(&task_code, new_status)?;
+            if let Some(c) = cache {
+                c.save_written_task(client, &task_code, None);
+            }
+        }
+        _ => {
+            let task = fetch_task(&task_code, global)?;
+            if !force {
+                workflows.check(&task.project.code, task.status, new_status)?;
+            }
+            writer.update_status(&task, new_status)?;
         }
+    }
+    if global.output.is_table() {
+        if writer.is_queue() {
+            print_queued(global);
+        } else {
+            println!("{}", "Task status updated".green().bold());
+        }
+        Ok(())
+    } else {
+        let data = HashMap::from([("code", task_code), ("status", format!("{new_status:?}"))]);
+        print_item(&data, global.output)
+    }
+}
+
+fn status_many(
+    codes: &[String],
+    selection: &TaskSelection,
+    status: String,
+    force: bool,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let new_status = parse_status(&status)?;
+    let writer = TaskWriter::new(global)?;
+    let workflows = if force {
+        Workflows::default()
+    } else {
+        Workflows::load()?
     };
-    if resp.status().is_success() {
-        println!("{}", "task created".green().bold())
-    } else if resp.status().is_client_error() {
-        let response: TaskErrorResponse = resp.json().unwrap();
-        println!("{}: {:?}", "error".red(), response);
+    let selected = selection.tasks(codes, global)?;
+    if !selection.confirm(&selected.tasks, &format!("Move to {new_status:?}"))? {
+        return Ok(());
+    }
+    let done = if writer.is_queue() {
+        "queued"
     } else {
-        println!("{}: {}", "Error".red().bold(), resp.status());
-        println!("{}: {}", "Error".red().bold(), resp.text().unwrap());
+        "status updated"
     };
+    run_each(selected, global.output, done, |t| {
+        workflows.check(&t.project.code, t.status, new_status)?;
+        writer.update_status(t, new_status)
+    })
 }
 
-fn status_update(task_code: String, status: String) {
-    let new_status = match Status::from_str(&status) {
-        Ok(s) => s,
-        Err(err) => {
-            eprintln!("{}: {err}", TASK_STATUS_ERROR.red().bold());
-            std::process::exit(1);
-        }
+fn transition(args: TransitionArgs, transition: Transition, global: &GlobalArgs) -> Result<()> {
+    let client = get_client(global)?;
+    let cache = cache(global);
+    let workflows = if args.force {
+        Workflows::default()
+    } else {
+        Workflows::load()?
     };
-    let request = match get_request(TASK_ENDPOINT, Some(&task_code)) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
+    let mut changes = Vec::new();
+    let mut failed = 0;
+    for code in &args.task_codes {
+        match move_task(&client, cache.as_ref(), code, transition, &workflows) {
+            Ok(change) => {
+                if global.output.is_table() {
+                    println!(
+                        "{}: {} -> {}",
+                        change.code.bold(),
+                        change.before,
+                        change.after
+                    );
+                }
+                changes.push(change);
+            }
+            Err(err) => {
+                failed += 1;
+                eprintln!("{}: {err}", code.bold());
+            }
         }
+    }
+    if !global.output.is_table() {
+        print_list(&changes, global.output)?;
+    }
+    if failed > 0 {
+        return Err(CliError::Partial(format!(
+            "{failed} of {} tasks not updated",
+            args.task_codes.len()
+        )));
+    }
+    Ok(())
+}
+
+fn move_task(
+    client: &UmsebenziClient,
+    cache: Option<&Cache>,
+    code: &str,
+    transition: Transition,
+    workflows: &Workflows,
+) -> Result<StatusChange> {
+    let task = client.get_task(code)?;
+    let before = task.status;
+    let Some(after) = transition.target(before) else {
+        return Err(CliError::input(format!(
+            "no status after or before {before}"
+        )));
     };
-    let mut data = HashMap::new();
-    data.insert("status", new_status.to_value());
+    workflows.check(&task.project.code, before, after)?;
+    let skipped = skipped(before, after);
+    if !skipped.is_empty() {
+        let names: Vec<String> = skipped.iter().map(|s| format!("{s:?}")).collect();
+        eprintln!(
+            "{}: {code} skips {}",
+            "Warning".yellow().bold(),
+            names.join(", ")
+        );
+    }
+    if after != before {
+        client.update_status(code, after)?;
+        if let Some(c) = cache {
+            c.save_written_task(client, code, None);
+        }
+    }
+    Ok(StatusChange {
+        code: code.to_string(),
+        before,
+        after,
+    })
+}
 
-    // Add /status/ to url
-    let url = request.url + "/status";
+fn edit(task_code: String, force: bool, global: &GlobalArgs) -> Result<()> {
+    let task = fetch_task(&task_code, global)?;
 
-    let resp = match request.client.patch(url).json(&data).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
-        }
+    let mut title = input("Title [leave blank to use existing]")?;
+    if title.is_empty() {
+        title = task.title.clone()
+    }
+
+    let mut description = input("Description: [Type E to edit. leave blank to use existing]")?;
+    if description.is_empty() {
+        description = task.description.clone()
+    } else if description == "E" {
+        description = text_editor(Some(task.description.clone()))?;
+    }
+
+    show_issue_options();
+    let current_issue = Issue::from_api_str(&task.issue)
+        .map_err(|err| CliError::input(format!("{TASK_ISSUE_ERROR}: {err}")))?;
+    let issue_value = input("Issue [leave blank to use existing]")?;
+    let issue = if issue_value.is_empty() {
+        current_issue.clone()
+    } else {
+        parse_issue(&issue_value)?
     };
-    if resp.status().is_success() {
-        println!("{}", "Task status updated".green().bold())
-    } else if resp.status().is_client_error() {
-        let response: TaskErrorResponse = resp.json().unwrap();
-        println!("{}: {:?}", "error".red(), response);
+    let parent_id = if current_issue == Issue::EPIC && issue == Issue::SUBTASK {
+        Some(prompt_parent()?)
+    } else if current_issue == Issue::SUBTASK && issue == Issue::EPIC {
+        None
     } else {
-        println!("{}: {}", "Error".red().bold(), resp.status());
-        println!("{}: {}", "Error".red().bold(), resp.text().unwrap());
+        task.parent
     };
-}
 
-fn edit(task_code: String) {
-    let task: TaskResponse;
-    let request = match get_request(TASK_ENDPOINT, Some(&task_code)) {
-        Ok(c) => c,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_ERROR.red());
-            std::process::exit(1);
-        }
+    show_status_options(false);
+    let status_value = input("Status [leave blank to use existing]")?;
+    let status = if status_value.is_empty() {
+        task.status
+    } else {
+        parse_status(&status_value)?
     };
-    let resp = match request.client.get(request.url).send() {
-        Ok(r) => r,
-        Err(err) => {
-            eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-            std::process::exit(1);
-        }
+    if !force {
+        Workflows::load()?.check(&task.project.code, task.status, status)?;
+    }
+
+    let mut task_upadate = TaskRequest {
+        title,
+        description,
+        issue: issue.to_value(),
+        parent_id,
+        status: status.to_value(),
+        ..update_request(&task)?
     };
-    // Update existing task
-    if resp.status().is_success() {
-        task = match resp.json() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{} {err}", "Unable to parse response json".red());
-                std::process::exit(1);
-            }
-        };
 
-        print!("{}: ", "Title [leave blank to use existing]".green().bold());
-        let _ = io::stdout().flush();
-        let mut title_buf = String::new();
-        io::stdin()
-            .read_line(&mut title_buf)
-            .expect(&TASK_TITLE_ERROR.red());
-        if title_buf.trim().is_empty() {
-            title_buf = task.title
-        }
-
-        print!(
-            "{}: ",
-            "Description: [Type E to edit. leave blank to use existing]"
-                .green()
-                .bold()
-        );
-        let _ = io::stdout().flush();
-        let mut description_buf = String::new();
-        io::stdin()
-            .read_line(&mut description_buf)
-            .expect(&TASK_DESCRIPTION_ERROR.red());
-        if description_buf.trim().is_empty() {
-            description_buf = task.description
-        } else if description_buf.trim() == "E" {
-            description_buf =
-                text_editor(Some(task.description)).expect(&TASK_DESCRIPTION_ERROR.red());
-        }
-
-        show_issue_options();
-        let issue: Issue;
-        let current_issue = Issue::from_api_str(&task.issue).expect(TASK_ISSUE_ERROR);
-        print!("{}: ", "Issue [leave blank to use existing]".green().bold());
-        let _ = io::stdout().flush();
-        let mut issue_buf = String::new();
-        io::stdin()
-            .read_line(&mut issue_buf)
-            .expect(&TASK_ISSUE_ERROR.red().bold());
-
-        if issue_buf.trim().is_empty() {
-            issue = current_issue.clone();
-        } else {
-            issue = match Issue::from_str(&issue_buf.trim()) {
-                Ok(i) => i,
-                Err(err) => {
-                    eprintln!("{}: {err}", TASK_ISSUE_ERROR.red().bold());
-                    std::process::exit(1);
-                }
-            };
-        }
-        let mut parent_id = None;
-        if current_issue == Issue::EPIC && issue == Issue::SUBTASK {
-            print!("{}: ", "Parent Task ID".green().bold());
-            let _ = io::stdout().flush();
-            let mut parent_buf = String::new();
-            io::stdin()
-                .read_line(&mut parent_buf)
-                .expect(&TASK_PARENT_ERROR.red().bold());
-            if parent_buf.trim().is_empty() {
-                eprintln!("{}", TASK_PARENT_ERROR.red().bold());
-                std::process::exit(1);
-            }
-            match parent_buf.trim().parse::<i32>() {
-                Ok(i) => {
-                    parent_id = Some(i);
-                }
-                Err(_) => {
-                    eprintln!("{}", "Task parent ID is should be a number".red().bold());
-                    std::process::exit(1);
-                }
-            };
-        } else if current_issue == Issue::SUBTASK && issue == Issue::EPIC {
-            parent_id = None;
+    if global.offline {
+        Queue::open(global)?.push(Change::Edit {
+            code: task.code.clone(),
+            task: task_upadate.clone(),
+            modified_at: Some(task.modified_at.clone()),
+        })?;
+        print_queued(global);
+        return if global.output.is_table() {
+            Ok(())
         } else {
-            parent_id = task.parent
-        }
-
-        // let status = TaskStatus::from_api_string(&task.status).expect("Invalid task status");
-        // let status = TaskStatus::from_api_string(&task.status).expect("Invalid task status");
-        let assign_id;
-        match task.assigned_to.id{
-            UserID::IntId(i) =>{
-                assign_id = i.to_string();
-            },
-            UserID::UUID(s) =>{
-                assign_id = s;
-            }
-        }
-        let task_upadate = TaskRequest {
-            project_id: task.project.id,
-            title: title_buf,
-            description: description_buf,
-            issue: issue.to_value(),
-            due_date: task.due_date,
-            assigned_to_id: assign_id,
-            parent_id: parent_id,
-            status: task.status.to_value(),
+            print_item(&task_upadate, global.output)
         };
+    }
+    let client = get_client(global)?;
+    let updated = send_with_retry(
+        &mut task_upadate,
+        |t| client.update_task(&task_code, t),
+        reprompt_field,
+    )?;
+    if let Some(c) = cache(global) {
+        c.save_written_task(&client, &task.code, Some(updated.clone()));
+    }
+    if global.output.is_table() {
+        println!("{}", "Task Updated".green().bold());
+        Ok(())
+    } else {
+        print_item(&updated, global.output)
+    }
+}
 
-        let request = match get_request(TASK_ENDPOINT, Some(&task_code)) {
-            Ok(c) => c,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        let resp = match request.client.put(request.url).json(&task_upadate).send() {
-            Ok(r) => r,
-            Err(err) => {
-                eprint!("{}: {err}", CLIENT_RESPONSE_ERROR.red());
-                std::process::exit(1);
-            }
-        };
-        if resp.status().is_success() {
-            println!("{}", "Task Updated".green().bold())
-        } else if resp.status().is_client_error() {
-            let response: TaskErrorResponse = resp.json().unwrap();
-            println!("{}: {:?}", "error".red(), response);
-        } else {
-            println!("{}: {}", "Error".red().bold(), resp.status());
-            println!("{}: {}", "Error".red().bold(), resp.text().unwrap());
-        };
-    } else if resp.status().is_client_error() {
-        eprintln!("{}: Unable to find task code", "Error".red());
+// Request that leaves every field of the task unchanged
+pub fn update_request(task: &TaskResponse) -> Result<TaskRequest> {
+    let issue = Issue::from_api_str(&task.issue).map_err(|_| {
+        CliError::input(format!(
+            "{} has an unknown issue type '{}'",
+            task.code, task.issue
+        ))
+    })?;
+    Ok(TaskRequest {
+        project_id: task.project.id,
+        title: task.title.clone(),
+        description: task.description.clone(),
+        issue: issue.to_value(),
+        due_date: task.due_date.clone(),
+        assigned_to_id: task.assigned_to.id.to_string(),
+        parent_id: task.parent,
+        status: task.status.to_value(),
+    })
+}
+
+// Apply the edit flags to every selected task
+fn edit_many(args: TaskEditArgs, global: &GlobalArgs) -> Result<()> {
+    if args.is_empty() {
+        return Err(CliError::input(
+            "Give the fields to change eg: --status, --assignee or --due",
+        ));
+    }
+    let description = match &args.description_file {
+        Some(path) => Some(read_description_file(path)?),
+        None => args.description.clone(),
+    };
+    let issue = args.issue.as_deref().map(parse_issue).transpose()?;
+    let status = args.status.as_deref().map(parse_status).transpose()?;
+    let writer = TaskWriter::new(global)?;
+    let workflows = if args.force {
+        Workflows::default()
     } else {
-        eprintln!(
-            "{}: Unable to fetch task details:  {:?}",
-            "Error".red().bold(),
-            resp.status()
-        );
-        eprintln!("{}: {:?}", "Error".red().bold(), resp.text().ok());
+        Workflows::load()?
+    };
+    let selected = args.selection.tasks(&args.task_codes, global)?;
+    if !args.selection.confirm(&selected.tasks, "Edit")? {
+        return Ok(());
     }
+    let done = if writer.is_queue() {
+        "queued"
+    } else {
+        "updated"
+    };
+    run_each(selected, global.output, done, |t| {
+        let mut request = update_request(t)?;
+        if let Some(title) = &args.title {
+            request.title = title.clone();
+        }
+        if let Some(d) = &description {
+            request.description = d.clone();
+        }
+        if let Some(i) = &issue {
+            request.issue = i.to_value();
+            if *i == Issue::EPIC {
+                request.parent_id = None;
+            }
+        }
+        if args.parent.is_some() {
+            request.parent_id = args.parent;
+        }
+        if request.issue == Issue::SUBTASK.to_value() && request.parent_id.is_none() {
+            return Err(CliError::input(format!(
+                "{TASK_PARENT_ERROR}, use --parent"
+            )));
+        }
+        if let Some(s) = status {
+            workflows.check(&t.project.code, t.status, s)?;
+            request.status = s.to_value();
+        }
+        if let Some(due) = &args.due {
+            request.due_date = Some(due.clone());
+        }
+        if let Some(a) = &args.assignee {
+            request.assigned_to_id = a.clone();
+        }
+        writer.update_task(t, &request)
+    })
 }
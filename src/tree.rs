@@ -0,0 +1,169 @@
+use serde::Serialize;
+use umsebenzi::enums::{Issue, Status};
+use umsebenzi::filter::{subtask_progress, TaskFilter};
+use umsebenzi::response::TaskResponse;
+
+use crate::cli_error::Result;
+use crate::output::print_list;
+use crate::task::{fetch_task, fetch_tasks};
+use crate::GlobalArgs;
+
+#[derive(Serialize)]
+struct TreeNode {
+    code: String,
+    title: String,
+    status: Status,
+    assigned_to: String,
+    due_date: Option<String>,
+    // Percentage of complete subtasks, epics only
+    complete: Option<usize>,
+    subtasks: Vec<TreeNode>,
+}
+
+fn is_done(status: Status) -> bool {
+    matches!(status, Status::COMPLETE | Status::ARCHIVE)
+}
+
+fn node(task: TaskResponse) -> TreeNode {
+    let complete = match subtask_progress(&task) {
+        Some((done, all)) if all > 0 => Some(done * 100 / all),
+        // An epic without subtasks is complete once it is done itself
+        Some(_) => Some(if is_done(task.status) { 100 } else { 0 }),
+        None => None,
+    };
+    TreeNode {
+        code: task.code,
+        title: task.title,
+        status: task.status,
+        assigned_to: task.assigned_to.to_string(),
+        due_date: task.due_date,
+        complete,
+        subtasks: Vec::new(),
+    }
+}
+
+// Subtasks go under their epic, those whose epic wasn't fetched stay at the top
+fn build(tasks: Vec<TaskResponse>) -> Vec<TreeNode> {
+    let (epics, subtasks): (Vec<_>, Vec<_>) = tasks
+        .into_iter()
+        .partition(|t| Issue::from_api_str(&t.issue).ok() != Some(Issue::SUBTASK));
+    let ids: Vec<i32> = epics.iter().map(|t| t.id).collect();
+    let mut nodes: Vec<TreeNode> = epics.into_iter().map(node).collect();
+    for task in subtasks {
+        match task.parent.and_then(|p| ids.iter().position(|id| *id == p)) {
+            Some(i) => nodes[i].subtasks.push(node(task)),
+            None => nodes.push(node(task)),
+        }
+    }
+    nodes
+}
+
+fn line(node: &TreeNode) -> String {
+    let mut line = format!(
+        "{} {}  {}  {}  due {}",
+        node.code,
+        node.title,
+        node.status,
+        node.assigned_to,
+        node.due_date.as_deref().unwrap_or("-")
+    );
+    if let Some(c) = node.complete {
+        line.push_str(&format!("  [{c}%]"));
+    }
+    line
+}
+
+fn lines(nodes: &[TreeNode]) -> Vec<String> {
+    let mut lines = Vec::new();
+    for node in nodes {
+        lines.push(line(node));
+        for (i, sub) in node.subtasks.iter().enumerate() {
+            let branch = if i + 1 == node.subtasks.len() {
+                "└── "
+            } else {
+                "├── "
+            };
+            lines.push(format!("{branch}{}", line(sub)));
+        }
+    }
+    lines
+}
+
+pub fn run(task_code: Option<String>, project: Option<String>, global: &GlobalArgs) -> Result<()> {
+    let tasks = match task_code {
+        Some(code) => {
+            let task = fetch_task(&code, global)?;
+            let filter = TaskFilter {
+                parent: Some(task.id),
+                ..Default::default()
+            };
+            let mut tasks = fetch_tasks(&filter, global)?;
+            tasks.insert(0, task);
+            tasks
+        }
+        None => fetch_tasks(
+            &TaskFilter {
+                project,
+                ..Default::default()
+            },
+            global,
+        )?,
+    };
+    let nodes = build(tasks);
+    if !global.output.is_table() {
+        return print_list(&nodes, global.output);
+    }
+    for line in lines(&nodes) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures;
+
+    fn task(
+        id: i32,
+        issue: &str,
+        status: &str,
+        parent: Option<i32>,
+        subtasks: &[&str],
+    ) -> TaskResponse {
+        fixtures::task(id)
+            .set("title", "Task")
+            .set("issue", issue)
+            .set("status", status)
+            .set("parent", parent)
+            .subtasks(subtasks)
+            .build()
+    }
+
+    #[test]
+    fn subtasks_are_nested() {
+        let tasks = vec![
+            task(
+                1,
+                "EPIC",
+                "IN_PROGRESS",
+                None,
+                &["COMPLETE", "TO_DO", "DRAFT"],
+            ),
+            task(2, "SUBTASK", "COMPLETE", Some(1), &[]),
+            task(3, "SUBTASK", "TO_DO", Some(1), &[]),
+            task(4, "EPIC", "COMPLETE", None, &[]),
+            task(5, "SUBTASK", "TO_DO", Some(9), &[]),
+        ];
+        let nodes = build(tasks);
+        let codes: Vec<&str> = nodes.iter().map(|n| n.code.as_str()).collect();
+        assert_eq!(codes, vec!["WEB-1", "WEB-4", "WEB-5"]);
+        assert_eq!(nodes[0].subtasks.len(), 2);
+        assert_eq!(nodes[0].complete, Some(33));
+        assert_eq!(nodes[1].complete, Some(100));
+        assert_eq!(nodes[2].complete, None);
+        let lines = lines(&nodes);
+        assert!(lines[1].starts_with("├── WEB-2"));
+        assert!(lines[2].starts_with("└── WEB-3"));
+    }
+}
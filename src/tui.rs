@@ -0,0 +1,494 @@
+use clap::Parser;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::layout::{Constraint, Flex, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use umsebenzi::client::UmsebenziClient;
+use umsebenzi::enums::{Issue, Status};
+use umsebenzi::filter::TaskFilter;
+use umsebenzi::request::TaskRequest;
+use umsebenzi::response::{ProjectResponse, TaskResponse};
+
+use crate::cli_error::{CliError, Result};
+use crate::description::text_editor;
+use crate::prompt::require_interactive;
+use crate::service::{current_user, get_client};
+use crate::task::update_request;
+use crate::workflow::Workflows;
+use crate::GlobalArgs;
+
+#[derive(Parser, Debug)]
+pub struct TuiArgs {
+    /// Ignore the workflow rules in the config file
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pane {
+    Projects,
+    Tasks,
+    Detail,
+}
+
+enum Mode {
+    Browse,
+    // Picking a new status for the selected task
+    Status(ListState),
+    // Typing the title of a new subtask
+    Subtask(String),
+    // Waiting for y to delete the selected task
+    Delete,
+}
+
+struct App {
+    client: UmsebenziClient,
+    workflows: Workflows,
+    // Assignee of new subtasks, looked up before the terminal is in raw mode
+    // as it may ask for a passphrase
+    user: std::result::Result<String, String>,
+    projects: Vec<ProjectResponse>,
+    project_state: ListState,
+    tasks: Vec<TaskResponse>,
+    task_state: ListState,
+    pane: Pane,
+    mode: Mode,
+    // Result of the last action, shown in the bottom line
+    message: String,
+    quit: bool,
+}
+
+const HELP: &str =
+    "j/k move  enter open  h back  s status  e description  a subtask  d delete  r refresh  q quit";
+
+// Index after moving by step, staying within the list
+fn moved(current: Option<usize>, len: usize, step: isize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let current = current.unwrap_or(0) as isize;
+    Some((current + step).clamp(0, len as isize - 1) as usize)
+}
+
+fn status_colour(status: Status) -> Color {
+    match status {
+        Status::DRAFT | Status::READY => Color::Blue,
+        Status::TO_DO => Color::Yellow,
+        Status::IN_PROGRESS => Color::Green,
+        Status::REVIEW | Status::COMPLETE | Status::ARCHIVE => Color::Magenta,
+    }
+}
+
+fn status_span(status: Status) -> Span<'static> {
+    Span::styled(
+        format!("{status:?}"),
+        Style::default().fg(status_colour(status)),
+    )
+}
+
+// The editor needs the normal screen, the TUI is restored once it exits
+fn edit_outside(terminal: &mut DefaultTerminal, text: Option<String>) -> Result<String> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
+    let edited = text_editor(text);
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    enable_raw_mode()?;
+    terminal.clear()?;
+    edited
+}
+
+impl App {
+    fn new(client: UmsebenziClient, global: &GlobalArgs, force: bool) -> Result<Self> {
+        let user = current_user(global, Some(&client)).map_err(|err| err.to_string());
+        let mut app = App {
+            client,
+            workflows: if force {
+                Workflows::default()
+            } else {
+                Workflows::load()?
+            },
+            user,
+            projects: Vec::new(),
+            project_state: ListState::default(),
+            tasks: Vec::new(),
+            task_state: ListState::default(),
+            pane: Pane::Projects,
+            mode: Mode::Browse,
+            message: HELP.to_string(),
+            quit: false,
+        };
+        app.projects = app.client.list_projects()?;
+        app.project_state.select(moved(None, app.projects.len(), 0));
+        app.load_tasks()?;
+        Ok(app)
+    }
+
+    fn project(&self) -> Option<&ProjectResponse> {
+        self.projects.get(self.project_state.selected()?)
+    }
+
+    fn task(&self) -> Option<&TaskResponse> {
+        self.tasks.get(self.task_state.selected()?)
+    }
+
+    fn load_tasks(&mut self) -> Result<()> {
+        self.tasks = match self.project() {
+            Some(p) => self.client.list_tasks(&TaskFilter {
+                project: Some(p.code.clone()),
+                ..Default::default()
+            })?,
+            None => Vec::new(),
+        };
+        let selected = self.task_state.selected();
+        self.task_state.select(moved(selected, self.tasks.len(), 0));
+        Ok(())
+    }
+
+    // Reload from the API, keeping the selected project and task
+    fn refresh(&mut self) -> Result<()> {
+        let project = self.project().map(|p| p.id);
+        let task = self.task().map(|t| t.id);
+        self.projects = self.client.list_projects()?;
+        let index = self.projects.iter().position(|p| Some(p.id) == project);
+        self.project_state
+            .select(index.or(moved(None, self.projects.len(), 0)));
+        self.load_tasks()?;
+        if let Some(i) = self.tasks.iter().position(|t| Some(t.id) == task) {
+            self.task_state.select(Some(i));
+        }
+        Ok(())
+    }
+
+    // Run an action on the selected task and refresh, errors are shown
+    // instead of closing the TUI
+    fn act(&mut self, action: impl FnOnce(&mut Self, &TaskResponse) -> Result<String>) {
+        let Some(task) = self.task().map(|t| t.code.clone()) else {
+            self.message = "No task selected".to_string();
+            return;
+        };
+        let result = self
+            .client
+            .get_task(&task)
+            .map_err(CliError::from)
+            .and_then(|t| action(self, &t))
+            .and_then(|done| self.refresh().map(|_| done));
+        self.message = match result {
+            Ok(done) => done,
+            Err(err) => format!("{task}: {err}"),
+        };
+    }
+
+    fn set_status(&mut self, status: Status) {
+        self.act(|app, task| {
+            app.workflows
+                .check(&task.project.code, task.status, status)?;
+            app.client.update_status(&task.code, status)?;
+            Ok(format!("{} moved to {status:?}", task.code))
+        });
+    }
+
+    fn edit_description(&mut self, terminal: &mut DefaultTerminal) {
+        self.act(|app, task| {
+            let description = edit_outside(terminal, Some(task.description.clone()))?;
+            let request = TaskRequest {
+                description,
+                ..update_request(task)?
+            };
+            app.client.update_task(&task.code, &request)?;
+            Ok(format!("{} description updated", task.code))
+        });
+    }
+
+    fn add_subtask(&mut self, terminal: &mut DefaultTerminal, title: String) {
+        self.act(|app, task| {
+            let assigned_to_id = app
+                .user
+                .clone()
+                .map_err(|err| CliError::input(format!("Unable to tell who you are, {err}")))?;
+            let description = edit_outside(terminal, None)?;
+            let request = TaskRequest {
+                project_id: task.project.id,
+                title,
+                description,
+                status: Status::DRAFT.to_value(),
+                issue: Issue::SUBTASK.to_value(),
+                due_date: None,
+                assigned_to_id,
+                // Subtasks of a subtask go to its epic
+                parent_id: Some(task.parent.unwrap_or(task.id)),
+            };
+            app.client.create_task(&request)?;
+            Ok(format!("Subtask added to {}", task.code))
+        });
+    }
+
+    fn delete(&mut self) {
+        self.act(|app, task| {
+            app.client.delete_task(&task.code)?;
+            Ok(format!("{} deleted", task.code))
+        });
+    }
+
+    fn key(&mut self, code: KeyCode, terminal: &mut DefaultTerminal) -> Result<()> {
+        match std::mem::replace(&mut self.mode, Mode::Browse) {
+            Mode::Status(mut state) => match code {
+                KeyCode::Char('j') | KeyCode::Down => {
+                    state.select(moved(state.selected(), Status::ALL.len(), 1));
+                    self.mode = Mode::Status(state);
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    state.select(moved(state.selected(), Status::ALL.len(), -1));
+                    self.mode = Mode::Status(state);
+                }
+                KeyCode::Enter => {
+                    if let Some(i) = state.selected() {
+                        self.set_status(Status::ALL[i]);
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('q') => {}
+                _ => self.mode = Mode::Status(state),
+            },
+            Mode::Subtask(mut title) => match code {
+                KeyCode::Enter if !title.trim().is_empty() => {
+                    self.add_subtask(terminal, title.trim().to_string())
+                }
+                KeyCode::Esc => {}
+                KeyCode::Backspace => {
+                    title.pop();
+                    self.mode = Mode::Subtask(title);
+                }
+                KeyCode::Char(c) => {
+                    title.push(c);
+                    self.mode = Mode::Subtask(title);
+                }
+                _ => self.mode = Mode::Subtask(title),
+            },
+            Mode::Delete => {
+                if code == KeyCode::Char('y') {
+                    self.delete();
+                } else {
+                    self.message = HELP.to_string();
+                }
+            }
+            Mode::Browse => self.browse_key(code, terminal)?,
+        }
+        Ok(())
+    }
+
+    fn browse_key(&mut self, code: KeyCode, terminal: &mut DefaultTerminal) -> Result<()> {
+        let step = match code {
+            KeyCode::Char('j') | KeyCode::Down => 1,
+            KeyCode::Char('k') | KeyCode::Up => -1,
+            _ => 0,
+        };
+        if step != 0 {
+            if self.pane == Pane::Projects {
+                let selected = self.project_state.selected();
+                self.project_state
+                    .select(moved(selected, self.projects.len(), step));
+                self.task_state.select(None);
+                if let Err(err) = self.load_tasks() {
+                    self.message = err.to_string();
+                }
+            } else {
+                let selected = self.task_state.selected();
+                self.task_state
+                    .select(moved(selected, self.tasks.len(), step));
+            }
+            return Ok(());
+        }
+        let on_task = self.pane != Pane::Projects && self.task().is_some();
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => {
+                self.pane = match self.pane {
+                    Pane::Projects if !self.tasks.is_empty() => Pane::Tasks,
+                    Pane::Tasks => Pane::Detail,
+                    pane => pane,
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('h') | KeyCode::Left => {
+                self.pane = match self.pane {
+                    Pane::Detail => Pane::Tasks,
+                    _ => Pane::Projects,
+                }
+            }
+            KeyCode::Char('r') => {
+                self.message = match self.refresh() {
+                    Ok(_) => "Refreshed".to_string(),
+                    Err(err) => err.to_string(),
+                }
+            }
+            KeyCode::Char('s') if on_task => {
+                let mut state = ListState::default();
+                state.select(self.task().map(|t| t.status.position()));
+                self.mode = Mode::Status(state);
+            }
+            KeyCode::Char('e') if on_task => self.edit_description(terminal),
+            KeyCode::Char('a') if on_task => self.mode = Mode::Subtask(String::new()),
+            KeyCode::Char('d') if on_task => {
+                if let Some(t) = self.task() {
+                    self.message = format!("Delete {} and its subtasks? y/n", t.code);
+                }
+                self.mode = Mode::Delete;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn block(&self, title: &str, pane: Pane) -> Block<'static> {
+        let style = if self.pane == pane {
+            Style::default().fg(Color::Cyan)
+        } else {
+            Style::default()
+        };
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(style)
+            .title(title.to_string())
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, bottom] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+        let [projects, tasks, detail] = Layout::horizontal([
+            Constraint::Percentage(20),
+            Constraint::Percentage(35),
+            Constraint::Percentage(45),
+        ])
+        .areas(main);
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+
+        let items: Vec<ListItem> = self
+            .projects
+            .iter()
+            .map(|p| ListItem::new(format!("{} {}", p.code, p.title)))
+            .collect();
+        let list = List::new(items)
+            .block(self.block("Projects", Pane::Projects))
+            .highlight_style(highlight);
+        frame.render_stateful_widget(list, projects, &mut self.project_state);
+
+        let items: Vec<ListItem> = self
+            .tasks
+            .iter()
+            .map(|t| {
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{:<8} ", t.code)),
+                    status_span(t.status),
+                    Span::raw(format!(" {}", t.title)),
+                ]))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(self.block("Tasks", Pane::Tasks))
+            .highlight_style(highlight);
+        frame.render_stateful_widget(list, tasks, &mut self.task_state);
+
+        let lines = self.task().map(detail_lines).unwrap_or_default();
+        let paragraph = Paragraph::new(lines)
+            .block(self.block("Detail", Pane::Detail))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, detail);
+
+        let bottom_text = match &self.mode {
+            Mode::Subtask(title) => format!("Subtask title: {title}_"),
+            _ => self.message.clone(),
+        };
+        frame.render_widget(Paragraph::new(bottom_text), bottom);
+
+        if let Mode::Status(state) = &mut self.mode {
+            let [area] = Layout::horizontal([Constraint::Length(20)])
+                .flex(Flex::Center)
+                .areas(main);
+            let [area] = Layout::vertical([Constraint::Length(Status::ALL.len() as u16 + 2)])
+                .flex(Flex::Center)
+                .areas(area);
+            let items: Vec<ListItem> = Status::ALL
+                .iter()
+                .map(|s| ListItem::new(status_span(*s)))
+                .collect();
+            let list = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title("Status"))
+                .highlight_style(highlight);
+            frame.render_widget(Clear, area);
+            frame.render_stateful_widget(list, area, state);
+        }
+    }
+}
+
+fn detail_lines(task: &TaskResponse) -> Vec<Line<'static>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let field = |name: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{name}: "), bold),
+            Span::raw(value),
+        ])
+    };
+    let mut lines = vec![
+        field("Title", task.title.clone()),
+        field("Code", task.code.clone()),
+        Line::from(vec![
+            Span::styled("Status: ", bold),
+            status_span(task.status),
+        ]),
+        field("Issue", task.issue.clone()),
+        field("Assigned To", task.assigned_to.to_string()),
+        field("Due Date", task.due_date.clone().unwrap_or_default()),
+        field("Created By", task.created_by.to_string()),
+        Line::default(),
+    ];
+    lines.extend(task.description.lines().map(|l| Line::from(l.to_string())));
+    if let Some(subtasks) = task.subtasks.as_ref().filter(|s| !s.is_empty()) {
+        lines.push(Line::default());
+        lines.push(Line::styled("Subtasks", bold));
+        for s in subtasks {
+            lines.push(Line::from(format!("{} {} {}", s.code, s.status, s.title)));
+        }
+    }
+    lines
+}
+
+pub fn run(args: TuiArgs, global: &GlobalArgs) -> Result<()> {
+    require_interactive("The TUI needs a terminal", "the task commands")?;
+    let client = get_client(global)?;
+    let mut app = App::new(client, global, args.force)?;
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut app, &mut terminal);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(app: &mut App, terminal: &mut DefaultTerminal) -> Result<()> {
+    while !app.quit {
+        terminal.draw(|frame| app.draw(frame))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press {
+                app.key(key.code, terminal)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_stays_in_the_list() {
+        assert_eq!(moved(None, 0, 1), None);
+        assert_eq!(moved(None, 3, 0), Some(0));
+        assert_eq!(moved(Some(2), 3, 1), Some(2));
+        assert_eq!(moved(Some(0), 3, -1), Some(0));
+        assert_eq!(moved(Some(0), 3, 1), Some(1));
+    }
+}
@@ -0,0 +1,178 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use umsebenzi::enums::Status;
+
+use crate::cli_error::{CliError, Result};
+use crate::config::read_config_file_or_default;
+
+// Rules used for projects without their own entry in [workflow]
+const DEFAULT_WORKFLOW: &str = "default";
+
+// Status name to the statuses it may move to
+pub type WorkflowRules = BTreeMap<String, Vec<String>>;
+
+// Status change made by the task start, review, done, archive, next and back verbs
+#[derive(Clone, Copy, Debug)]
+pub enum Transition {
+    To(Status),
+    Next,
+    Back,
+}
+
+impl Transition {
+    // None when there is no status after or before the current one
+    pub fn target(self, current: Status) -> Option<Status> {
+        match self {
+            Transition::To(s) => Some(s),
+            Transition::Next => current.next(),
+            Transition::Back => current.previous(),
+        }
+    }
+}
+
+// Statuses passed over when moving forward more than one step
+pub fn skipped(from: Status, to: Status) -> Vec<Status> {
+    if to.position() <= from.position() {
+        return Vec::new();
+    }
+    Status::ALL[from.position() + 1..to.position()].to_vec()
+}
+
+// Allowed status changes per project code, from the [workflow] section of
+// the config file. Statuses without a rule can move to any status
+#[derive(Default)]
+pub struct Workflows(BTreeMap<String, WorkflowRules>);
+
+fn parse_status(name: &str) -> Result<Status> {
+    Status::from_str(name).map_err(|err| CliError::Config(format!("[workflow] {err}")))
+}
+
+impl Workflows {
+    // Project codes are matched case insensitively
+    fn new(rules: BTreeMap<String, WorkflowRules>) -> Self {
+        Workflows(
+            rules
+                .into_iter()
+                .map(|(project, r)| (project.to_lowercase(), r))
+                .collect(),
+        )
+    }
+
+    // No rules without a config file, an unreadable one is an error
+    pub fn load() -> Result<Self> {
+        Ok(Workflows::new(read_config_file_or_default()?.workflow))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn check(&self, project: &str, from: Status, to: Status) -> Result<()> {
+        if from == to {
+            return Ok(());
+        }
+        let Some((name, rules)) = self
+            .0
+            .get_key_value(&project.to_lowercase())
+            .or_else(|| self.0.get_key_value(DEFAULT_WORKFLOW))
+        else {
+            return Ok(());
+        };
+        for (status, allowed) in rules {
+            if parse_status(status)? != from {
+                continue;
+            }
+            let allowed = allowed
+                .iter()
+                .map(|s| parse_status(s))
+                .collect::<Result<Vec<Status>>>()?;
+            if allowed.contains(&to) {
+                return Ok(());
+            }
+            let names: Vec<String> = allowed.iter().map(|s| format!("{s:?}")).collect();
+            let names = if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            };
+            return Err(CliError::input(format!(
+                "{from:?} to {to:?} is not allowed by the {name} workflow, allowed: {names}. Use --force to change it anyway"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+pub struct StatusChange {
+    pub code: String,
+    pub before: Status,
+    pub after: Status,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skipped_steps() {
+        assert_eq!(
+            skipped(Status::READY, Status::IN_PROGRESS),
+            vec![Status::TO_DO]
+        );
+        assert!(skipped(Status::TO_DO, Status::IN_PROGRESS).is_empty());
+        assert!(skipped(Status::REVIEW, Status::IN_PROGRESS).is_empty());
+    }
+
+    #[test]
+    fn workflow_rules() {
+        let workflows = Workflows::new(
+            toml::from_str(
+                r#"
+[web]
+draft = ["ready"]
+in-progress = ["review", "todo"]
+archive = []
+
+[default]
+complete = ["archive"]
+"#,
+            )
+            .unwrap(),
+        );
+        assert!(workflows.check("WEB", Status::DRAFT, Status::READY).is_ok());
+        assert!(workflows
+            .check("WEB", Status::DRAFT, Status::COMPLETE)
+            .is_err());
+        assert!(workflows
+            .check("WEB", Status::ARCHIVE, Status::DRAFT)
+            .is_err());
+        assert!(workflows
+            .check("WEB", Status::ARCHIVE, Status::ARCHIVE)
+            .is_ok());
+        // Statuses without a rule are not restricted
+        assert!(workflows
+            .check("WEB", Status::REVIEW, Status::DRAFT)
+            .is_ok());
+        assert!(workflows
+            .check("APP", Status::COMPLETE, Status::DRAFT)
+            .is_err());
+        assert!(workflows
+            .check("APP", Status::DRAFT, Status::COMPLETE)
+            .is_ok());
+    }
+
+    #[test]
+    fn next_and_back() {
+        assert_eq!(
+            Transition::Next.target(Status::REVIEW),
+            Some(Status::COMPLETE)
+        );
+        assert_eq!(Transition::Back.target(Status::DRAFT), None);
+        assert_eq!(
+            Transition::To(Status::COMPLETE).target(Status::DRAFT),
+            Some(Status::COMPLETE)
+        );
+    }
+}